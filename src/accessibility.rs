@@ -0,0 +1,94 @@
+// Accessibility options - colour vision, shape cues, text size, motion and audio cues
+use crate::settings_state::next_in;
+use serde::{Deserialize, Serialize};
+
+/// Colour vision mode; anything but `Normal` swaps in a colour-blind-safe theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorVision {
+    #[default]
+    Normal,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorVision {
+    const ALL: [ColorVision; 4] = [
+        ColorVision::Normal,
+        ColorVision::Deuteranopia,
+        ColorVision::Protanopia,
+        ColorVision::Tritanopia,
+    ];
+
+    /// Built-in theme used instead of the selected one, if any
+    pub fn theme_id(self) -> Option<&'static str> {
+        match self {
+            ColorVision::Normal => None,
+            ColorVision::Deuteranopia => Some("deuteranopia"),
+            ColorVision::Protanopia => Some("protanopia"),
+            ColorVision::Tritanopia => Some("tritanopia"),
+        }
+    }
+
+    /// Catalog key of the mode's display name
+    pub fn label_key(self) -> &'static str {
+        match self {
+            ColorVision::Normal => "vision.normal",
+            ColorVision::Deuteranopia => "vision.deuteranopia",
+            ColorVision::Protanopia => "vision.protanopia",
+            ColorVision::Tritanopia => "vision.tritanopia",
+        }
+    }
+
+    pub fn step(self, forward: bool) -> Self {
+        next_in(&Self::ALL, self, forward)
+    }
+}
+
+/// Text size as a multiple of the base font size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextScale {
+    #[default]
+    Normal,
+    Large,
+    Huge,
+}
+
+impl TextScale {
+    const ALL: [TextScale; 3] = [TextScale::Normal, TextScale::Large, TextScale::Huge];
+
+    /// Press Start 2P is drawn on an 8px grid, so sizes stay on multiples of 8
+    pub fn point_size(self) -> u16 {
+        match self {
+            TextScale::Normal => 16,
+            TextScale::Large => 24,
+            TextScale::Huge => 32,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextScale::Normal => "100%",
+            TextScale::Large => "150%",
+            TextScale::Huge => "200%",
+        }
+    }
+
+    pub fn step(self, forward: bool) -> Self {
+        next_in(&Self::ALL, self, forward)
+    }
+}
+
+/// Persisted accessibility options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    pub color_vision: ColorVision,
+    /// Draw food, snake and walls with distinct shapes instead of relying on colour
+    pub shape_cues: bool,
+    pub text_scale: TextScale,
+    /// Disable the food pulse and screen effects
+    pub reduced_motion: bool,
+    /// Play a positional ping that points from the head towards the food
+    pub food_audio_cue: bool,
+}
@@ -0,0 +1,232 @@
+// Achievements - goals unlocked by play, defined in TOML so new ones need no code
+use crate::assets::AssetResolver;
+use crate::game_state::{Direction, GameState};
+use crate::locale::Locale;
+use crate::persistence::Profile;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+/// Directory (inside an assets root) that holds extra achievement definitions
+pub const ACHIEVEMENTS_DIR: &str = "achievements";
+
+/// Definitions compiled into the binary
+const BUILTIN_ACHIEVEMENTS: &str = include_str!("../assets/achievements/default.toml");
+
+/// A turn relative to the snake's heading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// The turn that changes heading `from` to `to`, if it is one
+    fn between(from: Direction, to: Direction) -> Option<Turn> {
+        let left_of = match from {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        };
+        if to == left_of {
+            Some(Turn::Left)
+        } else if to == left_of.opposite() {
+            Some(Turn::Right)
+        } else {
+            None
+        }
+    }
+}
+
+/// What has to happen for an achievement to unlock
+///
+/// See `assets/achievements/default.toml` for how each is written.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    Score { at_least: u32 },
+    BoardFill { percent: f32 },
+    FoodStreak { count: u32, seconds: f32 },
+    MinSpeedSurvival { seconds: f32 },
+    NoTurn { turn: Turn, min_score: u32 },
+    GamesPlayed { at_least: u32 },
+    TotalFood { at_least: u32 },
+}
+
+/// Achievement - one goal and what unlocks it
+#[derive(Debug, Clone, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    name: String,
+    description: String,
+    pub condition: Condition,
+}
+
+impl Achievement {
+    /// Name in the current language, or as written in the definition
+    pub fn name<'a>(&'a self, locale: &'a Locale) -> &'a str {
+        locale
+            .lookup(&format!("achievement.{}.name", self.id))
+            .unwrap_or(&self.name)
+    }
+
+    /// Description in the current language, or as written in the definition
+    pub fn description<'a>(&'a self, locale: &'a Locale) -> &'a str {
+        locale
+            .lookup(&format!("achievement.{}.description", self.id))
+            .unwrap_or(&self.description)
+    }
+}
+
+/// A definitions file: any number of `[[achievement]]` entries
+#[derive(Deserialize)]
+struct AchievementFile {
+    #[serde(default)]
+    achievement: Vec<Achievement>,
+}
+
+fn parse(text: &str) -> Result<Vec<Achievement>, String> {
+    toml::from_str::<AchievementFile>(text)
+        .map(|file| file.achievement)
+        .map_err(|e| format!("Invalid achievements: {}", e))
+}
+
+fn load_file(path: &Path) -> Result<Vec<Achievement>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse(&text)
+}
+
+/// Built-in achievements plus any defined under `<assets>/achievements/`
+pub fn load_definitions(assets: &AssetResolver) -> Vec<Achievement> {
+    let builtins = parse(BUILTIN_ACHIEVEMENTS).map_err(|e| eprintln!("{}", e)).unwrap_or_default();
+    assets.merge_from_disk(ACHIEVEMENTS_DIR, "achievements", builtins, |a| &a.id, load_file)
+}
+
+/// AchievementTracker - watches games as they are played and unlocks achievements
+///
+/// Unlocks are saved to the profile straight away.
+pub struct AchievementTracker {
+    definitions: Vec<Achievement>,
+    unlocked: Vec<String>,
+    /// Tick of the last step seen; a smaller one means a new game started
+    last_tick: u32,
+    last_direction: Direction,
+    /// Game time of each recent food, oldest first
+    food_times: VecDeque<u64>,
+    /// Game time spent at the fastest speed
+    min_speed_ms: u64,
+    turned_left: bool,
+    turned_right: bool,
+}
+
+impl AchievementTracker {
+    pub fn new(definitions: Vec<Achievement>) -> Self {
+        AchievementTracker {
+            definitions,
+            unlocked: Profile::load().achievements.into_keys().collect(),
+            last_tick: 0,
+            last_direction: Direction::Right,
+            food_times: VecDeque::new(),
+            min_speed_ms: 0,
+            turned_left: false,
+            turned_right: false,
+        }
+    }
+
+    /// Look at the game after a call to `GameState::update`; returns what it unlocked
+    pub fn step(&mut self, game: &GameState, food_eaten: bool) -> Vec<Achievement> {
+        let tick = game.tick();
+        if tick == self.last_tick {
+            return Vec::new();
+        }
+        if tick < self.last_tick || self.last_tick == 0 {
+            self.start_game();
+        }
+        self.last_tick = tick;
+
+        match Turn::between(self.last_direction, game.direction) {
+            Some(Turn::Left) => self.turned_left = true,
+            Some(Turn::Right) => self.turned_right = true,
+            None => {}
+        }
+        self.last_direction = game.direction;
+
+        if food_eaten {
+            self.food_times.push_back(game.elapsed_ms);
+        }
+        if game.at_min_speed() && !game.game_over {
+            self.min_speed_ms += game.current_speed_ms as u64;
+        }
+
+        self.unlock_where(|tracker, condition| match *condition {
+            Condition::Score { at_least } => game.score >= at_least,
+            Condition::BoardFill { percent } => {
+                let playable = game.occupancy().free_count() + game.snake.len();
+                game.snake.len() as f32 >= playable as f32 * percent / 100.0
+            }
+            Condition::FoodStreak { count, seconds } => {
+                let window = (seconds * 1000.0) as u64;
+                let recent = tracker
+                    .food_times
+                    .iter()
+                    .filter(|&&t| game.elapsed_ms - t <= window)
+                    .count();
+                food_eaten && recent >= count as usize
+            }
+            Condition::MinSpeedSurvival { seconds } => tracker.min_speed_ms >= (seconds * 1000.0) as u64,
+            _ => false,
+        })
+    }
+
+    /// Check end-of-game and lifetime achievements once a finished game is in the profile
+    pub fn finish(&mut self, game: &GameState) -> Vec<Achievement> {
+        let profile = Profile::load();
+        self.unlock_where(|tracker, condition| match *condition {
+            Condition::NoTurn { turn, min_score } => {
+                let turned = match turn {
+                    Turn::Left => tracker.turned_left,
+                    Turn::Right => tracker.turned_right,
+                };
+                !game.walls.is_empty() && game.score >= min_score && !turned
+            }
+            Condition::GamesPlayed { at_least } => profile.games_played >= at_least,
+            Condition::TotalFood { at_least } => profile.total_food >= at_least,
+            _ => false,
+        })
+    }
+
+    fn start_game(&mut self) {
+        // Every game starts heading right
+        self.last_direction = Direction::Right;
+        self.food_times.clear();
+        self.min_speed_ms = 0;
+        self.turned_left = false;
+        self.turned_right = false;
+    }
+
+    /// Unlock and save every locked achievement whose condition holds
+    fn unlock_where(&mut self, holds: impl Fn(&Self, &Condition) -> bool) -> Vec<Achievement> {
+        let newly: Vec<Achievement> = self
+            .definitions
+            .iter()
+            .filter(|a| !self.unlocked.contains(&a.id) && holds(self, &a.condition))
+            .cloned()
+            .collect();
+        if newly.is_empty() {
+            return newly;
+        }
+
+        let mut profile = Profile::load();
+        for achievement in &newly {
+            self.unlocked.push(achievement.id.clone());
+            profile.unlock(&achievement.id);
+        }
+        if let Err(e) = profile.save() {
+            eprintln!("Failed to save achievements: {}", e);
+        }
+        newly
+    }
+}
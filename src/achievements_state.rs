@@ -0,0 +1,56 @@
+use crate::achievements::Achievement;
+use crate::locale::Locale;
+use crate::persistence::Profile;
+use std::collections::BTreeMap;
+
+pub struct AchievementsState {
+    pub definitions: Vec<Achievement>,
+    /// Unlocked ids, with when each was unlocked
+    pub unlocked: BTreeMap<String, u64>,
+    /// Index of the highlighted achievement; the list scrolls to keep it in view
+    pub selected: usize,
+}
+
+impl AchievementsState {
+    pub fn new(definitions: Vec<Achievement>) -> Self {
+        AchievementsState {
+            definitions,
+            unlocked: Profile::load().achievements,
+            selected: 0,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.unlocked = Profile::load().achievements;
+        self.selected = 0;
+    }
+
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.contains_key(&achievement.id)
+    }
+
+    /// Description to list under the achievement's name, marked if still locked
+    pub fn description(&self, achievement: &Achievement, locale: &Locale) -> String {
+        let description = achievement.description(locale).to_string();
+        if self.is_unlocked(achievement) {
+            description
+        } else {
+            locale.format("achievements.locked", &[("description", description)])
+        }
+    }
+
+    /// How many of the defined achievements are unlocked
+    pub fn unlocked_count(&self) -> usize {
+        self.definitions.iter().filter(|a| self.is_unlocked(a)).count()
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.definitions.len() {
+            self.selected += 1;
+        }
+    }
+}
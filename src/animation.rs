@@ -0,0 +1,295 @@
+// Animation - a shared clock, easing curves, tweens and screen transitions
+//
+// Everything animated reads time from one `Clock` instead of `Instant::now()`,
+// so animations freeze with the clock and can be stepped frame by frame.
+use crate::input_handler::AppState;
+
+/// Clock - seconds of animation time, advanced once per frame
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    now: f32,
+    paused: bool,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current animation time in seconds
+    pub fn now(&self) -> f32 {
+        self.now
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Move time forward by a frame's real duration, unless paused
+    pub fn advance(&mut self, dt: f32) {
+        if !self.paused {
+            self.step(dt);
+        }
+    }
+
+    /// Move time forward by exactly `dt`, even while paused
+    pub fn step(&mut self, dt: f32) {
+        self.now += dt.max(0.0);
+    }
+}
+
+/// Easing curves mapping linear progress 0..1 to eased progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    QuadOut,
+    CubicInOut,
+    CubicOut,
+    /// Overshoots past the end, then settles back
+    BackOut,
+    /// Drops onto the end and bounces to rest
+    BounceOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::BackOut => {
+                const OVERSHOOT: f32 = 1.70158;
+                let u = t - 1.0;
+                1.0 + (OVERSHOOT + 1.0) * u * u * u + OVERSHOOT * u * u
+            }
+            Easing::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                if t < 1.0 / D {
+                    N * t * t
+                } else if t < 2.0 / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// Tween - a value moving from `from` to `to` over `duration` seconds of clock time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween {
+    pub from: f32,
+    pub to: f32,
+    start: f32,
+    duration: f32,
+    easing: Easing,
+}
+
+impl Tween {
+    pub fn new(from: f32, to: f32, start: f32, duration: f32, easing: Easing) -> Self {
+        Tween {
+            from,
+            to,
+            start,
+            duration,
+            easing,
+        }
+    }
+
+    /// A tween that has already finished at `value`
+    pub fn at(value: f32) -> Self {
+        Tween::new(value, value, 0.0, 0.0, Easing::Linear)
+    }
+
+    /// Linear progress 0..1 at time `now`
+    pub fn progress(&self, now: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        ((now - self.start) / self.duration).clamp(0.0, 1.0)
+    }
+
+    pub fn value(&self, now: f32) -> f32 {
+        self.from + (self.to - self.from) * self.easing.apply(self.progress(now))
+    }
+
+    pub fn is_done(&self, now: f32) -> bool {
+        self.progress(now) >= 1.0
+    }
+}
+
+/// How one screen gives way to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// The new screen fades in from the background colour
+    Fade,
+    /// A background-coloured curtain slides off to the left, revealing the new screen
+    SlideLeft,
+    /// The curtain slides off to the right
+    SlideRight,
+}
+
+/// Transition - a screen change in progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    tween: Tween,
+}
+
+impl Transition {
+    const DURATION: f32 = 0.3;
+
+    /// Transition for a change between two screens
+    ///
+    /// Entering or leaving play fades; moving deeper into the menus slides left
+    /// and coming back slides right. With reduced motion every change fades.
+    pub fn between(from: AppState, to: AppState, now: f32, reduced_motion: bool) -> Self {
+        let kind = match (from, to) {
+            _ if reduced_motion => TransitionKind::Fade,
+            (AppState::Playing, _) | (_, AppState::Playing) => TransitionKind::Fade,
+            (_, AppState::MainMenu) | (AppState::Credits, AppState::Settings) => TransitionKind::SlideRight,
+            _ => TransitionKind::SlideLeft,
+        };
+        let easing = match kind {
+            TransitionKind::Fade => Easing::QuadOut,
+            TransitionKind::SlideLeft | TransitionKind::SlideRight => Easing::CubicInOut,
+        };
+        Transition {
+            kind,
+            tween: Tween::new(0.0, 1.0, now, Self::DURATION, easing),
+        }
+    }
+
+    /// Eased progress 0..1; the new screen is fully shown at 1
+    pub fn progress(&self, now: f32) -> f32 {
+        self.tween.value(now)
+    }
+
+    pub fn is_done(&self, now: f32) -> bool {
+        self.tween.is_done(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_EASINGS: [Easing; 6] = [
+        Easing::Linear,
+        Easing::QuadOut,
+        Easing::CubicInOut,
+        Easing::CubicOut,
+        Easing::BackOut,
+        Easing::BounceOut,
+    ];
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-5, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn clock_advances_until_paused() {
+        let mut clock = Clock::new();
+        clock.advance(0.25);
+        assert_close(clock.now(), 0.25);
+
+        clock.set_paused(true);
+        clock.advance(1.0);
+        assert_close(clock.now(), 0.25);
+
+        clock.set_paused(false);
+        clock.advance(0.5);
+        assert_close(clock.now(), 0.75);
+    }
+
+    #[test]
+    fn clock_steps_while_paused_and_never_goes_back() {
+        let mut clock = Clock::new();
+        clock.set_paused(true);
+        clock.step(0.1);
+        assert_close(clock.now(), 0.1);
+
+        clock.step(-1.0);
+        assert_close(clock.now(), 0.1);
+    }
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in ALL_EASINGS {
+            assert_close(easing.apply(0.0), 0.0);
+            assert_close(easing.apply(1.0), 1.0);
+            // Progress outside 0..1 is clamped
+            assert_close(easing.apply(-0.5), 0.0);
+            assert_close(easing.apply(1.5), 1.0);
+        }
+    }
+
+    #[test]
+    fn back_out_overshoots_before_settling() {
+        assert!((0..100).any(|i| Easing::BackOut.apply(i as f32 / 100.0) > 1.0));
+    }
+
+    #[test]
+    fn tween_interpolates_over_its_duration() {
+        let tween = Tween::new(10.0, 20.0, 1.0, 2.0, Easing::Linear);
+        assert_close(tween.value(0.0), 10.0);
+        assert_close(tween.value(1.0), 10.0);
+        assert_close(tween.value(2.0), 15.0);
+        assert_close(tween.value(3.0), 20.0);
+        assert_close(tween.value(9.0), 20.0);
+
+        assert!(!tween.is_done(2.9));
+        assert!(tween.is_done(3.0));
+    }
+
+    #[test]
+    fn tween_applies_its_easing() {
+        let tween = Tween::new(0.0, 100.0, 0.0, 1.0, Easing::QuadOut);
+        assert_close(tween.value(0.5), 75.0);
+    }
+
+    #[test]
+    fn finished_tween_holds_its_value() {
+        let tween = Tween::at(4.0);
+        assert!(tween.is_done(0.0));
+        assert_close(tween.value(0.0), 4.0);
+    }
+
+    #[test]
+    fn transition_progresses_and_completes() {
+        let transition = Transition::between(AppState::MainMenu, AppState::Settings, 5.0, false);
+        assert_close(transition.progress(5.0), 0.0);
+        assert!(!transition.is_done(5.0));
+
+        let midway = transition.progress(5.0 + Transition::DURATION / 2.0);
+        assert!(midway > 0.0 && midway < 1.0);
+
+        assert_close(transition.progress(5.0 + Transition::DURATION), 1.0);
+        assert!(transition.is_done(5.0 + Transition::DURATION));
+    }
+
+    #[test]
+    fn transition_kind_follows_navigation() {
+        let kind = |from, to, reduced_motion| Transition::between(from, to, 0.0, reduced_motion).kind;
+        assert_eq!(kind(AppState::MainMenu, AppState::Playing, false), TransitionKind::Fade);
+        assert_eq!(kind(AppState::MainMenu, AppState::Settings, false), TransitionKind::SlideLeft);
+        assert_eq!(kind(AppState::Settings, AppState::MainMenu, false), TransitionKind::SlideRight);
+        assert_eq!(kind(AppState::Credits, AppState::Settings, false), TransitionKind::SlideRight);
+        assert_eq!(kind(AppState::MainMenu, AppState::Settings, true), TransitionKind::Fade);
+    }
+}
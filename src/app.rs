@@ -1,20 +1,47 @@
-use crate::audio::AudioSystem;
+use crate::achievements::{self, AchievementTracker};
+use crate::achievements_state::AchievementsState;
+use crate::animation::{Clock, Transition};
+use crate::asset_pack::AssetPack;
+use crate::assets::AssetResolver;
+use crate::audio::{AudioSystem, MusicTrack};
+use crate::capture::Capture;
 use crate::config::Config;
+use crate::credits_state::CreditsState;
+use crate::daily_state::DailyState;
 use crate::fonts::FontSystem;
 use crate::game_state::GameState;
+use crate::ghost::Ghost;
 use crate::input::{button_to_input, keycode_to_input};
 use crate::input_handler::{AppState, InputHandler};
 use crate::leaderboard_state::LeaderboardState;
+use crate::level::{self, level_name};
+use crate::locale::LocaleLibrary;
 use crate::menu_state::MenuState;
-use crate::persistence::Leaderboard;
-use crate::rendering::{GameRenderer, MenuRenderer};
-use sdl2::event::Event;
+use crate::persistence::{self, Settings};
+use crate::rendering::sprites::Tileset;
+use crate::rendering::{GameRenderer, MenuRenderer, ThemeLibrary};
+use crate::resources;
+use crate::settings_state::{Choice, SettingsState};
+use crate::stats_state::StatsState;
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::Keycode;
 use sdl2::render::Canvas;
 use sdl2::video::Window;
 use sdl2::{EventPump, GameControllerSubsystem, Sdl};
 use std::time::{Duration, Instant};
 
+/// Smallest window the screens are laid out for
+const MIN_WINDOW_WIDTH: u32 = 400;
+const MIN_WINDOW_HEIGHT: u32 = 480;
+
+/// How often the food direction cue repeats while playing
+const FOOD_CUE_INTERVAL: Duration = Duration::from_millis(1500);
+
 pub struct App {
+    // Renderers own textures, so they are declared (and dropped) before the canvas
+    menu_renderer: MenuRenderer,
+    game_renderer: GameRenderer,
+
     // SDL context
     _sdl_context: Sdl,
     canvas: Canvas<Window>,
@@ -22,35 +49,56 @@ pub struct App {
     _game_controller_subsystem: GameControllerSubsystem,
 
     // Game systems
+    assets: AssetResolver,
+    packs: Vec<AssetPack>,
+    themes: ThemeLibrary,
+    locales: LocaleLibrary,
     fonts: FontSystem,
     audio: AudioSystem,
-    menu_renderer: MenuRenderer,
-    game_renderer: GameRenderer,
+    capture: Capture,
+    achievements: AchievementTracker,
 
     // Game state
     app_state: AppState,
     menu_state: MenuState,
     game_state: GameState,
     leaderboard_state: LeaderboardState,
+    daily_state: DailyState,
+    stats_state: StatsState,
+    achievements_state: AchievementsState,
+    settings_state: SettingsState,
+    credits_state: CreditsState,
+    /// Best earlier run on the current seed, raced alongside the player
+    ghost: Option<Ghost>,
+    /// Whether the ghost was already looked up for the game at tick 0
+    ghost_checked: bool,
 
     // Timing
+    clock: Clock,
+    /// Screen change still animating, and the screen it was last started for
+    transition: Option<Transition>,
+    shown_state: AppState,
     last_update: Instant,
+    last_frame: Instant,
+    last_food_cue: Instant,
 }
 
 impl App {
-    pub fn new(config: Config) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(config: Config, mut assets: AssetResolver) -> Result<Self, Box<dyn std::error::Error>> {
         // Initialize SDL2
         let sdl_context = sdl2::init()?;
         let video_subsystem = sdl_context.video()?;
 
-        let window = video_subsystem
+        let mut window = video_subsystem
             .window(
                 "Neon Snake",
                 config.visual.window_width,
                 config.visual.window_height,
             )
             .position_centered()
+            .resizable()
             .build()?;
+        window.set_minimum_size(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT)?;
 
         let canvas = window.into_canvas().build()?;
         let event_pump = sdl_context.event_pump()?;
@@ -63,11 +111,28 @@ impl App {
             None
         };
 
+        // Layer the saved asset pack over the defaults before loading anything
+        let settings = Settings::load();
+        let packs = assets.available_packs();
+        assets.set_pack(
+            settings
+                .asset_pack
+                .as_ref()
+                .and_then(|id| packs.iter().find(|p| &p.id == id))
+                .cloned(),
+        );
+
+        let themes = ThemeLibrary::load(&assets);
+        let locales = LocaleLibrary::load(&assets);
+
         // Initialize font system and load fonts
-        let fonts = FontSystem::new()?;
+        let fonts = FontSystem::new(&assets, settings.accessibility.text_scale.point_size())?;
 
         // Initialize audio system and load sounds
-        let audio = AudioSystem::new()?;
+        let audio = AudioSystem::new(&assets)?;
+        let capture = Capture::new(&config.capture);
+        let achievement_definitions = achievements::load_definitions(&assets);
+        let achievements = AchievementTracker::new(achievement_definitions.clone());
 
         // Initialize renderers
         let menu_renderer = MenuRenderer::new(
@@ -81,59 +146,265 @@ impl App {
         let menu_state = MenuState::new();
         let game_state = GameState::new(config.clone());
         let leaderboard_state = LeaderboardState::new();
+        let daily_state = DailyState::new();
+        let stats_state = StatsState::new();
+        let achievements_state = AchievementsState::new(achievement_definitions);
+        let mut settings_state = SettingsState::new(settings);
+        settings_state.languages = locales
+            .locales()
+            .iter()
+            .map(|l| Choice {
+                id: l.id.clone(),
+                name: l.name.clone(),
+            })
+            .collect();
+        settings_state.themes = themes
+            .themes()
+            .iter()
+            .map(|t| Choice {
+                id: t.id.clone(),
+                name: t.name.clone(),
+            })
+            .collect();
+        settings_state.packs = packs
+            .iter()
+            .map(|p| Choice {
+                id: p.id.clone(),
+                name: p.manifest.pack.name.clone(),
+            })
+            .collect();
+        let credits_state = CreditsState::new();
 
         let last_update = Instant::now();
 
-        Ok(App {
+        let mut app = App {
             _sdl_context: sdl_context,
             canvas,
             event_pump,
             _game_controller_subsystem: game_controller_subsystem,
+            assets,
+            packs,
+            themes,
+            locales,
             fonts,
             audio,
+            capture,
+            achievements,
             menu_renderer,
             game_renderer,
             app_state,
             menu_state,
             game_state,
             leaderboard_state,
+            daily_state,
+            stats_state,
+            achievements_state,
+            settings_state,
+            credits_state,
+            ghost: None,
+            ghost_checked: false,
+            clock: Clock::new(),
+            transition: None,
+            shown_state: AppState::MainMenu,
             last_update,
-        })
+            last_frame: last_update,
+            last_food_cue: last_update,
+        };
+        app.apply_pack();
+        app.apply_theme();
+        app.apply_locale();
+        app.apply_level();
+        app.apply_display_mode();
+        app.relayout();
+        Ok(app)
+    }
+
+    /// Hand the selected theme, with any pack colour overrides, to every renderer
+    fn apply_theme(&mut self) {
+        // A colour vision mode takes precedence over the chosen theme
+        let settings = &self.settings_state.settings;
+        let theme_id = settings
+            .accessibility
+            .color_vision
+            .theme_id()
+            .or(settings.theme.as_deref());
+        let mut theme = self.themes.get(theme_id);
+        if let Some(pack) = self.assets.pack() {
+            theme = theme.with_overrides(&pack.manifest.colors);
+        }
+        // Themes with a tilesheet fall back to flat cells if it fails to load
+        let tileset = theme.tileset.as_ref().and_then(|spec| {
+            resources::load_texture(&self.canvas.texture_creator(), &self.assets, &spec.image)
+                .and_then(|texture| Tileset::new(texture, spec))
+                .map_err(|e| eprintln!("Failed to load tileset for theme {}: {}", theme.id, e))
+                .ok()
+        });
+        self.game_renderer.set_tileset(tileset);
+
+        self.menu_renderer.set_theme(theme.clone());
+        self.game_renderer.set_theme(theme);
+        self.game_renderer.set_accessibility(self.settings_state.settings.accessibility);
+        self.menu_renderer
+            .set_reduced_motion(self.settings_state.settings.accessibility.reduced_motion);
+    }
+
+    /// Hand the selected language's strings to every renderer
+    fn apply_locale(&mut self) {
+        let locale = self.locales.get(self.settings_state.settings.language.as_deref());
+        self.menu_renderer.set_locale(locale.clone());
+        self.game_renderer.set_locale(locale);
+    }
+
+    /// Push the active pack's levels and credits to the systems that use them
+    fn apply_pack(&mut self) {
+        let levels = self.assets.pack().map_or_else(Vec::new, |p| {
+            p.manifest
+                .levels
+                .iter()
+                .map(|path| Choice {
+                    id: path.clone(),
+                    name: level_name(path),
+                })
+                .collect()
+        });
+        self.settings_state.set_levels(levels);
+
+        let default_manifest = self.assets.default_manifest().map_err(|e| eprintln!("{}", e)).ok();
+        self.credits_state
+            .refresh(default_manifest.as_ref(), self.assets.pack().map(|p| &p.manifest));
+    }
+
+    /// Load the selected level's walls into the game (or clear them)
+    fn apply_level(&mut self) {
+        let walls = level::selected_walls(self.settings_state.settings.level.as_deref(), self.assets.pack());
+        self.game_state.set_walls(walls);
+    }
+
+    fn apply_display_mode(&mut self) {
+        if let Err(e) = self.settings_state.settings.display_mode.apply(self.canvas.window_mut()) {
+            eprintln!("Failed to change display mode: {}", e);
+        }
+    }
+
+    /// Fit the renderers to the current output size and font
+    fn relayout(&mut self) {
+        let (width, height) = self
+            .canvas
+            .output_size()
+            .unwrap_or_else(|_| self.canvas.window().size());
+        let hud_height = self.fonts.font().height().max(0) as u32 + 16;
+        self.game_renderer.resize(width, height, hud_height);
+        self.menu_renderer.resize(width, height);
+    }
+
+    /// React to a change made on the settings screen
+    fn apply_settings(&mut self) {
+        let selected_pack = self.settings_state.settings.asset_pack.clone();
+        let pack_changed = self.assets.pack().map(|p| &p.id) != selected_pack.as_ref();
+        if pack_changed {
+            let pack = selected_pack.and_then(|id| self.packs.iter().find(|p| p.id == id).cloned());
+            self.assets.set_pack(pack);
+
+            if let Err(e) = self.audio.reload(&self.assets) {
+                eprintln!("Failed to load pack sounds: {}", e);
+            }
+            self.apply_pack();
+        }
+
+        let point_size = self.settings_state.settings.accessibility.text_scale.point_size();
+        if pack_changed || point_size != self.fonts.point_size() {
+            match FontSystem::new(&self.assets, point_size) {
+                Ok(fonts) => self.fonts = fonts,
+                Err(e) => eprintln!("Failed to load font: {}", e),
+            }
+            self.relayout();
+        }
+
+        self.apply_display_mode();
+
+        self.apply_theme();
+        self.apply_locale();
+        self.apply_level();
+        self.settings_state.settings.save().ok();
     }
 
     pub fn run(&mut self) {
         'running: loop {
             // Handle events
+            let mut window_resized = false;
+            let mut screenshot_requested = false;
             for event in self.event_pump.poll_iter() {
                 match event {
                     Event::Quit { .. } => break 'running,
 
+                    Event::Window {
+                        win_event: WindowEvent::SizeChanged(..),
+                        ..
+                    } => window_resized = true,
+
+                    Event::KeyDown {
+                        keycode: Some(Keycode::F11),
+                        ..
+                    } => self.settings_state.toggle_fullscreen(),
+
+                    Event::KeyDown {
+                        keycode: Some(Keycode::F12),
+                        ..
+                    } => screenshot_requested = true,
+
+                    Event::KeyDown {
+                        keycode: Some(Keycode::F9),
+                        ..
+                    } => match self.capture.save_gif() {
+                        Ok(path) => {
+                            println!("Saving GIF to {}", path.display());
+                            self.audio.play_click();
+                        }
+                        Err(e) => eprintln!("GIF capture failed: {}", e),
+                    },
+
                     Event::KeyDown {
                         keycode: Some(keycode),
                         ..
                     } => {
                         if let Some(input) = keycode_to_input(keycode) {
-                            InputHandler::handle_input(
+                            let audio = &self.audio;
+                            let quit = InputHandler::handle_input(
                                 &mut self.app_state,
                                 &mut self.menu_state,
                                 &mut self.game_state,
                                 &mut self.leaderboard_state,
+                                &mut self.daily_state,
+                                &mut self.stats_state,
+                                &mut self.achievements_state,
+                                &mut self.settings_state,
                                 input,
-                                &self.audio,
+                                &|| audio.play_click(),
                             );
+                            if quit {
+                                break 'running;
+                            }
                         }
                     }
 
                     Event::ControllerButtonDown { button, .. } => {
                         if let Some(input) = button_to_input(button) {
-                            InputHandler::handle_input(
+                            let audio = &self.audio;
+                            let quit = InputHandler::handle_input(
                                 &mut self.app_state,
                                 &mut self.menu_state,
                                 &mut self.game_state,
                                 &mut self.leaderboard_state,
+                                &mut self.daily_state,
+                                &mut self.stats_state,
+                                &mut self.achievements_state,
+                                &mut self.settings_state,
                                 input,
-                                &self.audio,
+                                &|| audio.play_click(),
                             );
+                            if quit {
+                                break 'running;
+                            }
                         }
                     }
 
@@ -141,8 +412,45 @@ impl App {
                 }
             }
 
+            if window_resized {
+                self.relayout();
+            }
+
+            if self.settings_state.take_changed() {
+                self.apply_settings();
+            }
+
+            // A daily challenge may bring its own grid size
+            self.game_renderer.set_grid_size(self.game_state.grid_dimensions());
+
+            // Pack music follows the current screen
+            let track = match self.app_state {
+                AppState::Playing | AppState::Cleared => MusicTrack::Game,
+                _ => MusicTrack::Menu,
+            };
+            self.audio.play_music(track);
+
+            let frame_start = Instant::now();
+            let frame_dt = frame_start.duration_since(self.last_frame).as_secs_f32();
+            self.last_frame = frame_start;
+
+            // Animations stand still while the game is paused
+            self.clock
+                .set_paused(self.app_state == AppState::Playing && self.game_state.paused);
+            self.clock.advance(frame_dt);
+
             // Update game logic
             if self.app_state == AppState::Playing {
+                // A fresh game looks up the best earlier run on its seed
+                if self.game_state.tick() == 0 {
+                    if !self.ghost_checked {
+                        self.ghost = Ghost::load(&self.game_state);
+                        self.ghost_checked = true;
+                    }
+                } else {
+                    self.ghost_checked = false;
+                }
+
                 let now = Instant::now();
                 let elapsed = now.duration_since(self.last_update);
 
@@ -150,33 +458,130 @@ impl App {
                 self.game_state.update_interpolation(elapsed.as_millis() as u64);
 
                 if elapsed >= Duration::from_millis(self.game_state.current_speed_ms as u64) {
+                    let was_finished = self.game_state.finished();
                     let food_eaten = self.game_state.update();
                     if food_eaten {
                         self.audio.play_crunch();
+                        self.game_renderer.on_food_eaten(&self.game_state);
+                    }
+                    for achievement in self.achievements.step(&self.game_state, food_eaten) {
+                        self.game_renderer.show_achievement(&achievement);
+                    }
+
+                    // Point the player towards the food when it moves and then periodically
+                    let game = &self.game_state;
+                    if self.settings_state.settings.accessibility.food_audio_cue
+                        && !game.finished()
+                        && !game.paused
+                        && (food_eaten || self.last_food_cue.elapsed() >= FOOD_CUE_INTERVAL)
+                    {
+                        let head = game.snake.front().unwrap();
+                        self.audio.play_food_cue(game.food.x - head.x, game.food.y - head.y, game.grid_dimensions());
+                        self.last_food_cue = now;
                     }
 
-                    if self.game_state.game_over {
-                        // Check if it's a high score
-                        let mut leaderboard = Leaderboard::load();
-                        if leaderboard.is_high_score(self.game_state.score) {
-                            leaderboard.add_score("PLAYER".to_string(), self.game_state.score);
-                            leaderboard.save().ok();
+                    if self.game_state.finished() && !was_finished {
+                        // A win leaves play for its own screen; a death stays to burst the snake
+                        if self.game_state.cleared {
+                            self.game_renderer.on_board_cleared(&self.game_state);
+                            self.app_state = AppState::Cleared;
+                        } else {
+                            self.game_renderer.on_death(&self.game_state);
+                        }
+                        persistence::save_finished_game(
+                            &self.game_state,
+                            &level::mode_name(self.settings_state.settings.level.as_deref()),
+                        );
+                        for achievement in self.achievements.finish(&self.game_state) {
+                            self.game_renderer.show_achievement(&achievement);
                         }
                     }
 
                     self.last_update = now;
                 }
+
+                if let Some(ghost) = &mut self.ghost {
+                    ghost.sync(&self.game_state);
+                }
+            }
+            if matches!(self.app_state, AppState::Playing | AppState::Cleared) {
+                self.game_renderer.update_effects(&self.game_state, frame_dt, &self.clock);
+            }
+
+            if self.app_state != self.shown_state {
+                let reduced_motion = self.settings_state.settings.accessibility.reduced_motion;
+                self.transition = Some(Transition::between(
+                    self.shown_state,
+                    self.app_state,
+                    self.clock.now(),
+                    reduced_motion,
+                ));
+                self.shown_state = self.app_state;
             }
 
             // Render
             match self.app_state {
-                AppState::MainMenu => self.menu_renderer.render(&mut self.canvas, self.fonts.font(), &self.menu_state),
-                AppState::Playing => self.game_renderer.render_game(&mut self.canvas, self.fonts.font(), &self.game_state),
+                AppState::MainMenu => {
+                    self.menu_renderer
+                        .render(&mut self.canvas, &self.fonts, &self.menu_state, &self.clock)
+                }
+                AppState::Playing => {
+                    let ghost = self.ghost.as_ref().filter(|_| !self.settings_state.settings.hide_ghost);
+                    self.game_renderer
+                        .render_game(&mut self.canvas, &self.fonts, &self.game_state, ghost, &self.clock)
+                }
+                AppState::Cleared => {
+                    self.game_renderer
+                        .render_cleared(&mut self.canvas, &self.fonts, &self.game_state, &self.clock)
+                }
                 AppState::Leaderboard => {
                     self.game_renderer
-                        .render_leaderboard(&mut self.canvas, self.fonts.font(), &self.leaderboard_state)
+                        .render_leaderboard(&mut self.canvas, &self.fonts, &self.leaderboard_state)
+                }
+                AppState::Daily => {
+                    self.game_renderer
+                        .render_daily(&mut self.canvas, &self.fonts, &self.daily_state)
+                }
+                AppState::Stats => {
+                    self.game_renderer
+                        .render_stats(&mut self.canvas, &self.fonts, &self.stats_state)
+                }
+                AppState::Achievements => {
+                    self.game_renderer
+                        .render_achievements(&mut self.canvas, &self.fonts, &self.achievements_state)
+                }
+                AppState::Settings => {
+                    self.menu_renderer
+                        .render_settings(&mut self.canvas, &self.fonts, &self.settings_state, &self.clock)
+                }
+                AppState::Credits => {
+                    self.menu_renderer
+                        .render_credits(&mut self.canvas, &self.fonts, &self.credits_state)
+                }
+            }
+
+            if let Some(transition) = &self.transition {
+                if transition.is_done(self.clock.now()) {
+                    self.transition = None;
+                } else {
+                    self.menu_renderer.render_transition(&mut self.canvas, transition, &self.clock);
                 }
             }
+            // Captures read the finished frame back before it is presented
+            if screenshot_requested {
+                match self.capture.save_screenshot(&self.canvas) {
+                    Ok(path) => {
+                        println!("Saved screenshot to {}", path.display());
+                        self.audio.play_click();
+                    }
+                    Err(e) => eprintln!("Screenshot failed: {}", e),
+                }
+            }
+            if matches!(self.app_state, AppState::Playing | AppState::Cleared) {
+                self.capture.record_frame(&self.canvas);
+            }
+
+            self.canvas.present();
 
             // Frame rate limiting
             std::thread::sleep(Duration::from_millis(16)); // ~60 FPS
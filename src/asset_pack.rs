@@ -0,0 +1,207 @@
+// Asset packs - manifest-described directories or zip archives layered over the defaults
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Manifest file name at the root of every pack
+pub const MANIFEST_FILE: &str = "pack.toml";
+
+/// Directory (inside an assets root) that holds installed packs
+pub const PACKS_DIR: &str = "packs";
+
+// =============================================================================
+// Manifest
+// =============================================================================
+
+/// Top-level pack metadata shown on the credits screen
+#[derive(Debug, Deserialize, Clone)]
+pub struct PackInfo {
+    pub name: String,
+    pub author: String,
+    pub license: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Attribution for an individual asset inside a pack
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreditEntry {
+    pub asset: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub license: String,
+}
+
+/// Contents of `pack.toml`
+///
+/// Every section is optional; anything the pack leaves out falls back to the defaults.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PackManifest {
+    pub pack: PackInfo,
+    /// Font role (e.g. "main") to pack-relative path
+    #[serde(default)]
+    pub fonts: HashMap<String, String>,
+    /// Sound role (e.g. "click", "crunch") to pack-relative path
+    #[serde(default)]
+    pub sounds: HashMap<String, String>,
+    /// Music track (e.g. "menu", "game") to pack-relative path
+    #[serde(default)]
+    pub music: HashMap<String, String>,
+    /// Palette entry (e.g. "snake", "food") to RGB triple
+    #[serde(default)]
+    pub colors: HashMap<String, [u8; 3]>,
+    /// Pack-relative paths of level files
+    #[serde(default)]
+    pub levels: Vec<String>,
+    #[serde(default)]
+    pub credits: Vec<CreditEntry>,
+}
+
+impl PackManifest {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))
+    }
+}
+
+// =============================================================================
+// Packs
+// =============================================================================
+
+#[derive(Debug, Clone)]
+enum PackSource {
+    Dir(PathBuf),
+    Zip(PathBuf),
+}
+
+/// A pack on disk
+#[derive(Debug, Clone)]
+pub struct AssetPack {
+    /// Stable identifier (directory or archive name without extension)
+    pub id: String,
+    pub manifest: PackManifest,
+    source: PackSource,
+}
+
+/// Where a pack file can be loaded from
+pub enum PackFile {
+    Path(PathBuf),
+    Bytes(&'static [u8]),
+}
+
+impl AssetPack {
+    /// Open a pack directory or `.zip` archive and parse its manifest
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let source = if path.is_dir() {
+            PackSource::Dir(path.to_path_buf())
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+            PackSource::Zip(path.to_path_buf())
+        } else {
+            return Err(format!("Not an asset pack: {:?}", path));
+        };
+
+        let id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .ok_or_else(|| format!("Invalid pack path: {:?}", path))?;
+
+        let manifest_text = String::from_utf8(read_source(&source, MANIFEST_FILE)?)
+            .map_err(|e| format!("{} in {:?} is not UTF-8: {}", MANIFEST_FILE, path, e))?;
+        let manifest = PackManifest::parse(&manifest_text)?;
+
+        Ok(AssetPack { id, manifest, source })
+    }
+
+    /// Read a pack-relative file into memory
+    pub fn read(&self, relative: &str) -> Result<Vec<u8>, String> {
+        read_source(&self.source, relative)
+    }
+
+    /// Locate a pack-relative file, extracting archive entries into memory
+    ///
+    /// Archive entries are cached for the life of the process so SDL can borrow them.
+    pub fn file(&self, relative: &str) -> Result<PackFile, String> {
+        match &self.source {
+            PackSource::Dir(dir) => {
+                let path = dir.join(relative);
+                if path.is_file() {
+                    Ok(PackFile::Path(path))
+                } else {
+                    Err(format!("Pack '{}' is missing {}", self.id, relative))
+                }
+            }
+            PackSource::Zip(archive_path) => {
+                let cache = ARCHIVE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+                let mut cache = cache.lock().map_err(|_| "Archive cache poisoned".to_string())?;
+                let key = (archive_path.clone(), relative.to_string());
+                if let Some(bytes) = cache.get(&key) {
+                    return Ok(PackFile::Bytes(bytes));
+                }
+                let bytes: &'static [u8] = Box::leak(read_zip_entry(archive_path, relative)?.into_boxed_slice());
+                cache.insert(key, bytes);
+                Ok(PackFile::Bytes(bytes))
+            }
+        }
+    }
+}
+
+/// Archive entries extracted so far, keyed by (archive, entry)
+type ArchiveCache = Mutex<HashMap<(PathBuf, String), &'static [u8]>>;
+static ARCHIVE_CACHE: OnceCell<ArchiveCache> = OnceCell::new();
+
+fn read_source(source: &PackSource, relative: &str) -> Result<Vec<u8>, String> {
+    match source {
+        PackSource::Dir(dir) => {
+            let path = dir.join(relative);
+            fs::read(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))
+        }
+        PackSource::Zip(archive_path) => read_zip_entry(archive_path, relative),
+    }
+}
+
+fn read_zip_entry(archive_path: &Path, relative: &str) -> Result<Vec<u8>, String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", archive_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read archive {:?}: {}", archive_path, e))?;
+    let mut entry = archive
+        .by_name(relative)
+        .map_err(|e| format!("{} not found in {:?}: {}", relative, archive_path, e))?;
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to extract {} from {:?}: {}", relative, archive_path, e))?;
+    Ok(bytes)
+}
+
+/// Find every pack under `<root>/packs/` for the given asset roots
+///
+/// Earlier roots win when two packs share an id. Broken packs are reported and skipped.
+pub fn discover(roots: &[PathBuf]) -> Vec<AssetPack> {
+    let mut packs: Vec<AssetPack> = Vec::new();
+
+    for root in roots {
+        let Ok(entries) = fs::read_dir(root.join(PACKS_DIR)) else {
+            continue;
+        };
+
+        let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        paths.sort();
+
+        for path in paths {
+            match AssetPack::open(&path) {
+                Ok(pack) => {
+                    if !packs.iter().any(|p| p.id == pack.id) {
+                        packs.push(pack);
+                    }
+                }
+                Err(e) => eprintln!("Skipping asset pack {:?}: {}", path, e),
+            }
+        }
+    }
+
+    packs
+}
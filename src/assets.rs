@@ -0,0 +1,229 @@
+// Asset resolution - locates asset files across search paths with an embedded fallback
+use crate::asset_pack::{self, AssetPack, MANIFEST_FILE, PackFile, PackManifest};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that points at an assets directory
+pub const ASSETS_ENV_VAR: &str = "NEON_SNAKE_ASSETS";
+
+/// Directory name used under XDG data dirs
+const XDG_APP_DIR: &str = "neon-snake";
+
+// =============================================================================
+// Embedded Defaults
+// =============================================================================
+
+/// Default assets compiled into the binary, keyed by path relative to the assets root
+const EMBEDDED_ASSETS: &[(&str, &[u8])] = &[
+    ("pack.toml", include_bytes!("../assets/pack.toml")),
+    ("fonts/PressStart2P.ttf", include_bytes!("../assets/fonts/PressStart2P.ttf")),
+    ("fonts/DejaVuSansMono-Bold.ttf", include_bytes!("../assets/fonts/DejaVuSansMono-Bold.ttf")),
+    ("sounds/click.wav", include_bytes!("../assets/sounds/click.wav")),
+    ("sounds/crunch.wav", include_bytes!("../assets/sounds/crunch.wav")),
+    ("tiles/pixel.png", include_bytes!("../assets/tiles/pixel.png")),
+];
+
+fn embedded_asset(relative: &str) -> Option<&'static [u8]> {
+    EMBEDDED_ASSETS
+        .iter()
+        .find(|(name, _)| *name == relative)
+        .map(|(_, bytes)| *bytes)
+}
+
+// =============================================================================
+// Resolver
+// =============================================================================
+
+/// Where a resolved asset comes from
+#[derive(Debug, Clone)]
+pub enum AssetSource {
+    File(PathBuf),
+    Bytes(&'static [u8]),
+}
+
+/// Manifest section an asset role belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Font,
+    Sound,
+    Music,
+}
+
+/// AssetResolver - searches asset roots in priority order
+///
+/// Order: the active asset pack, `--assets` flag, `NEON_SNAKE_ASSETS`, the
+/// executable's directory, the working directory, XDG data dirs, then assets
+/// embedded in the binary.
+#[derive(Debug, Clone)]
+pub struct AssetResolver {
+    search_dirs: Vec<PathBuf>,
+    pack: Option<AssetPack>,
+}
+
+impl AssetResolver {
+    /// Build the search path list, with an optional directory from the command line
+    pub fn new(cli_dir: Option<PathBuf>) -> Self {
+        let mut search_dirs = Vec::new();
+
+        if let Some(dir) = cli_dir {
+            search_dirs.push(dir);
+        }
+
+        if let Some(dir) = env::var_os(ASSETS_ENV_VAR).filter(|v| !v.is_empty()) {
+            search_dirs.push(PathBuf::from(dir));
+        }
+
+        if let Some(exe_dir) = env::current_exe().ok().and_then(|p| p.parent().map(Path::to_path_buf)) {
+            search_dirs.push(exe_dir.join("assets"));
+        }
+
+        // Keeps `cargo run` from the repo root picking up edited assets
+        if let Ok(cwd) = env::current_dir() {
+            search_dirs.push(cwd.join("assets"));
+        }
+
+        for data_dir in xdg_data_dirs() {
+            search_dirs.push(data_dir.join(XDG_APP_DIR).join("assets"));
+        }
+
+        search_dirs.dedup();
+        AssetResolver { search_dirs, pack: None }
+    }
+
+    /// Merge the `.toml` files under `<root>/<dir>/` of every search root into `entries`
+    ///
+    /// `load` reads one file into any number of entries and `id` names an entry.
+    /// On-disk entries override built-ins with the same id; earlier roots win.
+    pub fn merge_from_disk<T>(
+        &self,
+        dir: &str,
+        what: &str,
+        mut entries: Vec<T>,
+        id: impl Fn(&T) -> &str,
+        load: impl Fn(&Path) -> Result<Vec<T>, String>,
+    ) -> Vec<T> {
+        let mut seen_on_disk: Vec<String> = Vec::new();
+        for root in &self.search_dirs {
+            let Ok(dir_entries) = fs::read_dir(root.join(dir)) else {
+                continue;
+            };
+            let mut paths: Vec<_> = dir_entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let loaded = match load(&path) {
+                    Ok(loaded) => loaded,
+                    Err(e) => {
+                        eprintln!("Skipping {} {:?}: {}", what, path, e);
+                        continue;
+                    }
+                };
+                for entry in loaded {
+                    if seen_on_disk.iter().any(|seen| seen == id(&entry)) {
+                        continue;
+                    }
+                    seen_on_disk.push(id(&entry).to_string());
+                    match entries.iter_mut().find(|e| id(e) == id(&entry)) {
+                        Some(existing) => *existing = entry,
+                        None => entries.push(entry),
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /// Packs installed under any of the search directories
+    pub fn available_packs(&self) -> Vec<AssetPack> {
+        asset_pack::discover(&self.search_dirs)
+    }
+
+    /// Layer a pack over the defaults (or remove the current one)
+    pub fn set_pack(&mut self, pack: Option<AssetPack>) {
+        self.pack = pack;
+    }
+
+    /// The pack currently layered over the defaults
+    pub fn pack(&self) -> Option<&AssetPack> {
+        self.pack.as_ref()
+    }
+
+    /// Manifest describing the default assets
+    pub fn default_manifest(&self) -> Result<PackManifest, String> {
+        let bytes = match self.resolve(MANIFEST_FILE)? {
+            AssetSource::File(path) => fs::read(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?,
+            AssetSource::Bytes(bytes) => bytes.to_vec(),
+        };
+        PackManifest::parse(&String::from_utf8_lossy(&bytes))
+    }
+
+    /// Look up a role in the active pack only
+    ///
+    /// Returns `None` when there is no pack or the pack does not override the role.
+    pub fn pack_asset(&self, kind: AssetKind, role: &str) -> Option<Result<AssetSource, String>> {
+        let pack = self.pack.as_ref()?;
+        let table = match kind {
+            AssetKind::Font => &pack.manifest.fonts,
+            AssetKind::Sound => &pack.manifest.sounds,
+            AssetKind::Music => &pack.manifest.music,
+        };
+        let relative = table.get(role)?;
+
+        Some(pack.file(relative).map(|file| match file {
+            PackFile::Path(path) => AssetSource::File(path),
+            PackFile::Bytes(bytes) => AssetSource::Bytes(bytes),
+        }))
+    }
+
+    /// Resolve a role from the active pack, falling back to the default asset
+    pub fn resolve_role(&self, kind: AssetKind, role: &str, default: &str) -> Result<AssetSource, String> {
+        match self.pack_asset(kind, role) {
+            Some(Ok(source)) => Ok(source),
+            Some(Err(e)) => {
+                eprintln!("Asset pack override for {} failed, using default: {}", role, e);
+                self.resolve(default)
+            }
+            None => self.resolve(default),
+        }
+    }
+
+    /// Resolve an asset by its path relative to the assets root
+    pub fn resolve(&self, relative: &str) -> Result<AssetSource, String> {
+        for dir in &self.search_dirs {
+            let path = dir.join(relative);
+            if path.is_file() {
+                return Ok(AssetSource::File(path));
+            }
+        }
+
+        embedded_asset(relative)
+            .map(AssetSource::Bytes)
+            .ok_or_else(|| format!("Asset not found: {} (searched: {:?})", relative, self.search_dirs))
+    }
+}
+
+/// XDG data directories: `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS`
+fn xdg_data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    match env::var_os("XDG_DATA_HOME").filter(|v| !v.is_empty()) {
+        Some(data_home) => dirs.push(PathBuf::from(data_home)),
+        None => {
+            if let Some(home) = env::var_os("HOME").filter(|v| !v.is_empty()) {
+                dirs.push(PathBuf::from(home).join(".local/share"));
+            }
+        }
+    }
+
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+
+    dirs
+}
@@ -1,32 +1,102 @@
 // Audio system using SDL2_mixer
+use crate::assets::AssetResolver;
 use crate::resources;
-use sdl2::mixer::{Channel, Chunk, AUDIO_S16LSB, DEFAULT_CHANNELS};
+use sdl2::mixer::{Channel, Chunk, Music, AUDIO_S16LSB, DEFAULT_CHANNELS};
+
+/// Background music tracks an asset pack may provide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicTrack {
+    Menu,
+    Game,
+}
+
+impl MusicTrack {
+    /// Key used for the track in a pack manifest's `[music]` table
+    fn manifest_key(self) -> &'static str {
+        match self {
+            MusicTrack::Menu => "menu",
+            MusicTrack::Game => "game",
+        }
+    }
+}
+
+/// Channel reserved for the food direction cue so effects never steal it
+const CUE_CHANNEL: Channel = Channel(0);
 
 /// AudioSystem - manages SDL2_mixer and sound playback
 pub struct AudioSystem {
     click_sound: Chunk,
     crunch_sound: Chunk,
+    cue_sound: Chunk,
+    menu_music: Option<Music<'static>>,
+    game_music: Option<Music<'static>>,
+    current_track: Option<MusicTrack>,
 }
 
 impl AudioSystem {
     /// Initialize the audio system and load sound effects
-    pub fn new() -> Result<Self, String> {
+    pub fn new(assets: &AssetResolver) -> Result<Self, String> {
         // Initialize SDL2_mixer with 44.1kHz, 16-bit, stereo, 1024 byte chunks
         sdl2::mixer::open_audio(44_100, AUDIO_S16LSB, DEFAULT_CHANNELS, 1024)?;
 
-        // Allocate 4 mixing channels for sound effects
+        // Allocate 4 mixing channels for sound effects, keeping the first for the food cue
         sdl2::mixer::allocate_channels(4);
+        sdl2::mixer::reserve_channels(1);
 
         // Load sound effects
-        let click_sound = resources::load_click_sound()?;
-        let crunch_sound = resources::load_crunch_sound()?;
+        let click_sound = resources::load_click_sound(assets)?;
+        let crunch_sound = resources::load_crunch_sound(assets)?;
 
         Ok(AudioSystem {
             click_sound,
             crunch_sound,
+            cue_sound: Self::synthesize_cue()?,
+            menu_music: Self::load_track(assets, MusicTrack::Menu),
+            game_music: Self::load_track(assets, MusicTrack::Game),
+            current_track: None,
         })
     }
 
+    /// Reload sounds and music after the active asset pack changes
+    pub fn reload(&mut self, assets: &AssetResolver) -> Result<(), String> {
+        Music::halt();
+        self.current_track = None;
+
+        self.click_sound = resources::load_click_sound(assets)?;
+        self.crunch_sound = resources::load_crunch_sound(assets)?;
+        self.menu_music = Self::load_track(assets, MusicTrack::Menu);
+        self.game_music = Self::load_track(assets, MusicTrack::Game);
+        Ok(())
+    }
+
+    fn load_track(assets: &AssetResolver, track: MusicTrack) -> Option<Music<'static>> {
+        match resources::load_music(assets, track.manifest_key())? {
+            Ok(music) => Some(music),
+            Err(e) => {
+                eprintln!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Short 880Hz sine ping with a linear fade, in the mixer's 16-bit stereo format
+    fn synthesize_cue() -> Result<Chunk, String> {
+        const SAMPLE_RATE: f32 = 44_100.0;
+        const DURATION_SECS: f32 = 0.08;
+        let frames = (SAMPLE_RATE * DURATION_SECS) as usize;
+
+        let samples: Vec<i16> = (0..frames)
+            .flat_map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                let fade = 1.0 - i as f32 / frames as f32;
+                let value = ((t * 880.0 * std::f32::consts::TAU).sin() * fade * 8_000.0) as i16;
+                [value, value]
+            })
+            .collect();
+
+        Chunk::from_raw_buffer(samples.into_boxed_slice())
+    }
+
     /// Play menu click sound
     pub fn play_click(&self) {
         let _ = Channel::all().play(&self.click_sound, 0);
@@ -36,4 +106,35 @@ impl AudioSystem {
     pub fn play_crunch(&self) {
         let _ = Channel::all().play(&self.crunch_sound, 0);
     }
+
+    /// Ping from the direction of the food relative to the snake's head
+    ///
+    /// Up on the grid is in front of the listener; distance lowers the volume.
+    pub fn play_food_cue(&self, dx: i32, dy: i32, (grid_width, grid_height): (u32, u32)) {
+        let angle = (dx as f32).atan2(-dy as f32).to_degrees().rem_euclid(360.0) as i16;
+        let max_distance = (grid_width + grid_height).max(1) as f32;
+        let distance = ((dx.abs() + dy.abs()) as f32 / max_distance * 200.0).min(200.0) as u8;
+
+        let _ = CUE_CHANNEL.set_position(angle, distance);
+        let _ = CUE_CHANNEL.play(&self.cue_sound, 0);
+    }
+
+    /// Loop the given music track, leaving it alone if it is already playing
+    pub fn play_music(&mut self, track: MusicTrack) {
+        if self.current_track == Some(track) {
+            return;
+        }
+        self.current_track = Some(track);
+
+        let music = match track {
+            MusicTrack::Menu => &self.menu_music,
+            MusicTrack::Game => &self.game_music,
+        };
+        match music {
+            Some(music) => {
+                let _ = music.play(-1);
+            }
+            None => Music::halt(),
+        }
+    }
 }
@@ -0,0 +1,156 @@
+// Capture - PNG screenshots and animated GIFs of the last few seconds of play
+use crate::config::CaptureConfig;
+use sdl2::image::SaveSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::Canvas;
+use sdl2::surface::Surface;
+use sdl2::video::Window;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Directory (relative to the working directory) captures are written to
+pub const CAPTURES_DIR: &str = "captures";
+
+/// NeuQuant sampling speed for GIF palettes: 1 is best quality, 30 fastest
+const GIF_QUANTIZE_SPEED: i32 = 10;
+
+/// One recorded GIF frame, already downscaled, as RGBA bytes
+struct GifFrame {
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+}
+
+/// Capture - takes screenshots and keeps a rolling buffer of recent frames
+pub struct Capture {
+    frames: VecDeque<GifFrame>,
+    max_frames: usize,
+    frame_interval: Duration,
+    max_width: u32,
+    last_frame: Option<Instant>,
+}
+
+impl Capture {
+    pub fn new(config: &CaptureConfig) -> Self {
+        let fps = config.gif_fps.clamp(1, 50);
+        Capture {
+            frames: VecDeque::new(),
+            max_frames: (config.gif_seconds * fps) as usize,
+            frame_interval: Duration::from_secs_f32(1.0 / fps as f32),
+            max_width: config.gif_max_width.max(16),
+            last_frame: None,
+        }
+    }
+
+    /// Save the frame being drawn as a PNG; call before presenting
+    pub fn save_screenshot(&self, canvas: &Canvas<Window>) -> Result<PathBuf, String> {
+        let (width, height) = canvas.output_size()?;
+        let mut pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32)?;
+        let surface = Surface::from_data(&mut pixels, width, height, width * 4, PixelFormatEnum::RGBA32)?;
+
+        let path = capture_path("png")?;
+        surface.save(&path)?;
+        Ok(path)
+    }
+
+    /// Add the frame being drawn to the GIF buffer, at most `gif_fps` times a second
+    pub fn record_frame(&mut self, canvas: &Canvas<Window>) {
+        if self.max_frames == 0 || self.last_frame.is_some_and(|t| t.elapsed() < self.frame_interval) {
+            return;
+        }
+        self.last_frame = Some(Instant::now());
+
+        let Ok((width, height)) = canvas.output_size() else {
+            return;
+        };
+        let pixels = match canvas.read_pixels(None, PixelFormatEnum::RGBA32) {
+            Ok(pixels) => pixels,
+            Err(e) => {
+                eprintln!("Failed to read frame for GIF: {}", e);
+                return;
+            }
+        };
+        let frame = downscale(&pixels, width, height, self.max_width);
+
+        // A GIF has one size throughout, so a resize starts the buffer over
+        if self
+            .frames
+            .back()
+            .is_some_and(|f| (f.width, f.height) != (frame.width, frame.height))
+        {
+            self.frames.clear();
+        }
+        if self.frames.len() == self.max_frames {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Write the buffered frames to a GIF on a background thread
+    ///
+    /// Returns the path the GIF will be written to.
+    pub fn save_gif(&mut self) -> Result<PathBuf, String> {
+        if self.frames.is_empty() {
+            return Err("Nothing recorded yet".to_string());
+        }
+
+        let path = capture_path("gif")?;
+        let frames: Vec<GifFrame> = self.frames.drain(..).collect();
+        let delay = (self.frame_interval.as_secs_f32() * 100.0).round() as u16;
+        let target = path.clone();
+        thread::spawn(move || {
+            if let Err(e) = write_gif(&target, frames, delay) {
+                eprintln!("Failed to save GIF {:?}: {}", target, e);
+            }
+        });
+        Ok(path)
+    }
+}
+
+fn write_gif(path: &Path, frames: Vec<GifFrame>, delay: u16) -> Result<(), String> {
+    let (width, height) = (frames[0].width, frames[0].height);
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[]).map_err(|e| e.to_string())?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(|e| e.to_string())?;
+
+    for mut frame in frames {
+        let mut gif_frame =
+            gif::Frame::from_rgba_speed(frame.width, frame.height, &mut frame.rgba, GIF_QUANTIZE_SPEED);
+        gif_frame.delay = delay;
+        encoder.write_frame(&gif_frame).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Nearest-neighbour shrink by a whole factor until the width fits `max_width`
+fn downscale(pixels: &[u8], width: u32, height: u32, max_width: u32) -> GifFrame {
+    let factor = width.div_ceil(max_width).max(1);
+    let (out_width, out_height) = (width / factor, height / factor);
+    let mut rgba = Vec::with_capacity((out_width * out_height * 4) as usize);
+    for y in 0..out_height {
+        let row = (y * factor * width) as usize * 4;
+        for x in 0..out_width {
+            let i = row + (x * factor) as usize * 4;
+            rgba.extend_from_slice(&[pixels[i], pixels[i + 1], pixels[i + 2], 255]);
+        }
+    }
+    GifFrame {
+        width: out_width as u16,
+        height: out_height as u16,
+        rgba,
+    }
+}
+
+/// A fresh, timestamped file name in the captures directory
+fn capture_path(extension: &str) -> Result<PathBuf, String> {
+    fs::create_dir_all(CAPTURES_DIR).map_err(|e| format!("Failed to create {}: {}", CAPTURES_DIR, e))?;
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    Ok(PathBuf::from(CAPTURES_DIR).join(format!("neon-snake-{}.{}", millis, extension)))
+}
@@ -0,0 +1,157 @@
+// Command-line argument parsing
+use std::path::{Path, PathBuf};
+
+/// Frames per second for rendered replays unless `--fps` says otherwise
+const DEFAULT_RENDER_FPS: u32 = 60;
+
+/// Options passed on the command line
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    /// Directory to search for assets before any other location
+    pub assets_dir: Option<PathBuf>,
+    /// Render a replay to video frames instead of opening the game
+    pub render: Option<RenderArgs>,
+    /// Play in the terminal instead of a window
+    pub terminal: bool,
+}
+
+/// What `--render-replay` writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// One YUV4MPEG2 video file (or stdout)
+    Y4m,
+    /// A directory of numbered PNG frames
+    Png,
+}
+
+/// Options for rendering a replay offline
+#[derive(Debug)]
+pub struct RenderArgs {
+    pub replay: PathBuf,
+    /// Output file or directory; `-` writes Y4M to stdout
+    pub output: PathBuf,
+    pub format: RenderFormat,
+    /// Frame size, defaulting to the configured window size
+    pub size: Option<(u32, u32)>,
+    pub fps: u32,
+}
+
+impl CliArgs {
+    /// Parse arguments from the process environment
+    pub fn parse() -> Result<Self, String> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    /// Parse arguments from an iterator (excluding the program name)
+    pub fn parse_from<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut cli = CliArgs::default();
+        let mut args = args.into_iter();
+
+        let mut replay = None;
+        let mut output = None;
+        let mut format = None;
+        let mut size = None;
+        let mut fps = None;
+
+        while let Some(arg) = args.next() {
+            if arg == "--terminal" {
+                cli.terminal = true;
+            } else if let Some(dir) = option_value(&arg, "--assets", &mut args)? {
+                cli.assets_dir = Some(PathBuf::from(dir));
+            } else if let Some(path) = option_value(&arg, "--render-replay", &mut args)? {
+                replay = Some(PathBuf::from(path));
+            } else if let Some(path) = option_value(&arg, "--output", &mut args)? {
+                output = Some(PathBuf::from(path));
+            } else if let Some(value) = option_value(&arg, "--format", &mut args)? {
+                format = Some(match value.as_str() {
+                    "y4m" => RenderFormat::Y4m,
+                    "png" => RenderFormat::Png,
+                    _ => return Err(format!("Unknown format {}, expected y4m or png", value)),
+                });
+            } else if let Some(value) = option_value(&arg, "--size", &mut args)? {
+                size = Some(parse_size(&value)?);
+            } else if let Some(value) = option_value(&arg, "--fps", &mut args)? {
+                fps = Some(
+                    value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|&fps| fps > 0)
+                        .ok_or_else(|| format!("Invalid frame rate: {}", value))?,
+                );
+            } else {
+                return Err(format!("Unknown argument: {}", arg));
+            }
+        }
+
+        match replay {
+            Some(replay) => {
+                let output = output.ok_or("--render-replay requires --output")?;
+                let format = format.unwrap_or_else(|| infer_format(&output));
+                if format == RenderFormat::Png && output == Path::new("-") {
+                    return Err("PNG frames cannot be written to stdout".to_string());
+                }
+                cli.render = Some(RenderArgs {
+                    replay,
+                    output,
+                    format,
+                    size,
+                    fps: fps.unwrap_or(DEFAULT_RENDER_FPS),
+                });
+            }
+            None if output.is_some() || format.is_some() || size.is_some() || fps.is_some() => {
+                return Err("--output, --format, --size and --fps need --render-replay".to_string());
+            }
+            None => {}
+        }
+
+        Ok(cli)
+    }
+}
+
+/// Value of `--name value` or `--name=value`, or None if `arg` is a different option
+fn option_value(
+    arg: &str,
+    name: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<Option<String>, String> {
+    if arg == name {
+        return args
+            .next()
+            .map(Some)
+            .ok_or_else(|| format!("{} requires a value", name));
+    }
+    Ok(arg
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(str::to_string))
+}
+
+/// Parse `WIDTHxHEIGHT`; both must be even for 4:2:0 video
+fn parse_size(value: &str) -> Result<(u32, u32), String> {
+    let invalid = || {
+        format!(
+            "Invalid size {}, expected WIDTHxHEIGHT such as 1280x720",
+            value
+        )
+    };
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: u32 = width.parse().map_err(|_| invalid())?;
+    let height: u32 = height.parse().map_err(|_| invalid())?;
+    if width < 2 || height < 2 || !width.is_multiple_of(2) || !height.is_multiple_of(2) {
+        return Err(format!("Size {} must be even in both dimensions", value));
+    }
+    Ok((width, height))
+}
+
+/// `.y4m` files and stdout get video; anything else is a directory of PNGs
+fn infer_format(output: &Path) -> RenderFormat {
+    let is_y4m = output == Path::new("-")
+        || output
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("y4m"));
+    if is_y4m {
+        RenderFormat::Y4m
+    } else {
+        RenderFormat::Png
+    }
+}
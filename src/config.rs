@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
 
 #[derive(Debug, Deserialize, Clone)]
@@ -6,14 +6,106 @@ pub struct Config {
     pub gameplay: GameplayConfig,
     pub visual: VisualConfig,
     pub audio: AudioConfig,
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    #[serde(default)]
+    pub input: InputConfig,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GameplayConfig {
     pub initial_speed_ms: u32,
     pub min_speed_ms: u32,
     pub speed_increase_per_food: u32,
-    pub grid_size: u32,
+    /// Square grid size, used for any dimension not set explicitly
+    #[serde(default)]
+    pub grid_size: Option<u32>,
+    #[serde(default)]
+    pub grid_width: Option<u32>,
+    #[serde(default)]
+    pub grid_height: Option<u32>,
+    #[serde(default)]
+    pub rules: RulesConfig,
+}
+
+impl GameplayConfig {
+    /// Smallest width that fits the default three-segment starting snake
+    const MIN_GRID_WIDTH: u32 = 4;
+
+    pub fn grid_width(&self) -> u32 {
+        self.grid_width.or(self.grid_size).unwrap_or(Self::MIN_GRID_WIDTH)
+    }
+
+    pub fn grid_height(&self) -> u32 {
+        self.grid_height.or(self.grid_size).unwrap_or(1)
+    }
+
+    /// Check the grid and rules; configs from `config.toml`, replays and daily boards all go through this
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.grid_width.or(self.grid_size).is_none() || self.grid_height.or(self.grid_size).is_none() {
+            return Err("gameplay: set grid_size, or both grid_width and grid_height".to_string());
+        }
+        if self.grid_width() < Self::MIN_GRID_WIDTH || self.grid_height() < 1 {
+            return Err(format!(
+                "gameplay: grid must be at least {}x1, got {}x{}",
+                Self::MIN_GRID_WIDTH,
+                self.grid_width(),
+                self.grid_height()
+            ));
+        }
+        // The snake starts in the middle row with its tail towards the left edge
+        let max_length = self.grid_width() / 2 + 1;
+        if self.rules.starting_length < 1 || self.rules.starting_length > max_length {
+            return Err(format!(
+                "gameplay.rules: starting_length must be 1 to {} on a {}-wide grid, got {}",
+                max_length,
+                self.grid_width(),
+                self.rules.starting_length
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How the move delay shrinks as food is eaten
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedCurve {
+    /// `speed_increase_per_food` ms off every food
+    #[default]
+    Linear,
+    /// The same fraction off every food: `speed_increase_per_food / initial_speed_ms`
+    Exponential,
+    /// All of a milestone's speed-up at once, every few foods
+    Stepped,
+}
+
+/// Rule variants under `[gameplay.rules]`; the defaults are the classic game
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RulesConfig {
+    /// Moving into the cell the tail is just leaving is allowed
+    pub tail_chase: bool,
+    /// Segments added per food
+    pub growth_per_food: u32,
+    pub starting_length: u32,
+    pub score_per_food: u32,
+    /// Each food scores the snake's length instead of `score_per_food`
+    pub score_by_length: bool,
+    pub speed_curve: SpeedCurve,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        RulesConfig {
+            tail_chase: false,
+            growth_per_food: 1,
+            starting_length: 3,
+            score_per_food: 10,
+            score_by_length: false,
+            speed_curve: SpeedCurve::Linear,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -21,6 +113,32 @@ pub struct VisualConfig {
     pub window_width: u32,
     pub window_height: u32,
     pub grid_alpha: f32,
+    /// Particle bursts and screen flashes; off skips them entirely
+    #[serde(default = "default_true")]
+    pub particles: bool,
+    /// Multiplier on how many particles each effect spawns
+    #[serde(default = "default_particle_density")]
+    pub particle_density: f32,
+    /// Additive halos around the snake and food
+    #[serde(default = "default_true")]
+    pub glow: bool,
+    /// CRT scanline and vignette overlay
+    #[serde(default)]
+    pub scanlines: bool,
+    /// Shake the playfield when the snake dies
+    #[serde(default = "default_true")]
+    pub screen_shake: bool,
+    /// Red/blue split flash when eating
+    #[serde(default = "default_true")]
+    pub chromatic_flash: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_particle_density() -> f32 {
+    1.0
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -28,10 +146,57 @@ pub struct AudioConfig {
     pub master_volume: f32,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CaptureConfig {
+    /// How many seconds of play the GIF hotkey saves
+    pub gif_seconds: u32,
+    pub gif_fps: u32,
+    /// Frames wider than this are shrunk by a whole factor
+    pub gif_max_width: u32,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        CaptureConfig {
+            gif_seconds: 10,
+            gif_fps: 15,
+            gif_max_width: 400,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct InputConfig {
+    /// Turns that can wait for coming moves, so presses faster than the snake moves aren't lost
+    pub queue_size: usize,
+    /// Show the waiting turns as arrows in the HUD
+    pub show_queue: bool,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        InputConfig {
+            queue_size: 3,
+            show_queue: false,
+        }
+    }
+}
+
+/// Default configuration compiled into the binary, used when no `config.toml` is found
+pub(crate) const DEFAULT_CONFIG: &str = include_str!("../config.toml");
+
 impl Config {
+    /// Load `config.toml` from the working directory, or the built-in defaults if there is none
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_str = fs::read_to_string("config.toml")?;
+        let config_str = match fs::read_to_string("config.toml") {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => DEFAULT_CONFIG.to_string(),
+            Err(e) => return Err(e.into()),
+        };
         let config: Config = toml::from_str(&config_str)?;
+        config.gameplay.validate()?;
         Ok(config)
     }
 }
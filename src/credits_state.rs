@@ -0,0 +1,52 @@
+use crate::asset_pack::PackManifest;
+
+/// One line on the credits screen
+#[derive(Debug, Clone)]
+pub struct CreditLine {
+    pub text: String,
+    pub is_heading: bool,
+}
+
+pub struct CreditsState {
+    pub lines: Vec<CreditLine>,
+}
+
+impl CreditsState {
+    pub fn new() -> Self {
+        CreditsState { lines: Vec::new() }
+    }
+
+    /// Rebuild the credits from the default assets and the active pack
+    pub fn refresh(&mut self, default_manifest: Option<&PackManifest>, pack_manifest: Option<&PackManifest>) {
+        self.lines.clear();
+        for manifest in [pack_manifest, default_manifest].into_iter().flatten() {
+            self.push_manifest(manifest);
+        }
+    }
+
+    fn push_manifest(&mut self, manifest: &PackManifest) {
+        let info = &manifest.pack;
+        self.lines.push(CreditLine {
+            text: format!("{} - {}", info.name, info.author),
+            is_heading: true,
+        });
+        if !info.description.is_empty() {
+            self.lines.push(CreditLine {
+                text: info.description.clone(),
+                is_heading: false,
+            });
+        }
+        self.lines.push(CreditLine {
+            text: info.license.clone(),
+            is_heading: false,
+        });
+
+        for credit in &manifest.credits {
+            let text = match &credit.author {
+                Some(author) => format!("{}: {} ({})", credit.asset, author, credit.license),
+                None => format!("{} ({})", credit.asset, credit.license),
+            };
+            self.lines.push(CreditLine { text, is_heading: false });
+        }
+    }
+}
@@ -0,0 +1,253 @@
+// Daily challenge - one board a day, the same for everyone, derived from the date
+//
+// The ruleset comes only from the date, never from `config.toml`, so every
+// copy of the game builds the identical board. Days follow UTC. The board is
+// drawn from SplitMix64, specified here, so no library update can change it;
+// the game places food with the same generator.
+use crate::config::{GameplayConfig, RulesConfig};
+use crate::game_state::Position;
+use crate::replay::REPLAYS_DIR;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Grid sizes a daily board can have, as (width, height)
+const GRID_SIZES: &[(u32, u32)] = &[(20, 20), (24, 24), (30, 30), (32, 18), (40, 22)];
+
+/// Move delays shared by every daily board before modifiers
+const INITIAL_SPEED_MS: u32 = 150;
+const MIN_SPEED_MS: u32 = 50;
+const SPEED_INCREASE_PER_FOOD: u32 = 5;
+
+/// How the board is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyMode {
+    OpenArena,
+    /// Short wall segments scattered over the board
+    Obstacles,
+}
+
+impl DailyMode {
+    /// Catalog key of the mode's name
+    pub fn label_key(self) -> &'static str {
+        match self {
+            DailyMode::OpenArena => "daily.open_arena",
+            DailyMode::Obstacles => "daily.obstacles",
+        }
+    }
+}
+
+/// A twist on the usual speed rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// Starts at a brisk pace
+    QuickStart,
+    /// Never speeds up
+    Steady,
+    /// Speeds up twice as much per food
+    Frenzy,
+}
+
+impl Modifier {
+    /// Catalog key of the modifier's name
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Modifier::QuickStart => "daily.quick_start",
+            Modifier::Steady => "daily.steady",
+            Modifier::Frenzy => "daily.frenzy",
+        }
+    }
+
+    fn apply(self, gameplay: &mut GameplayConfig) {
+        match self {
+            Modifier::QuickStart => gameplay.initial_speed_ms = 100,
+            Modifier::Steady => gameplay.speed_increase_per_food = 0,
+            Modifier::Frenzy => gameplay.speed_increase_per_food *= 2,
+        }
+    }
+}
+
+/// DailyChallenge - the board, rules and food seed for one calendar day
+#[derive(Debug, Clone)]
+pub struct DailyChallenge {
+    /// UTC date as `YYYY-MM-DD`
+    pub date: String,
+    /// Seed for food placement
+    pub seed: u64,
+    pub mode: DailyMode,
+    pub modifiers: Vec<Modifier>,
+    pub gameplay: GameplayConfig,
+    pub walls: Vec<Position>,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self::for_date(&today())
+    }
+
+    pub fn for_date(date: &str) -> Self {
+        let seed = fnv1a(date.as_bytes());
+        let mut rng = SplitMix64::new(seed);
+
+        let (width, height) = GRID_SIZES[rng.below(GRID_SIZES.len() as u32) as usize];
+        let mode = if rng.chance(0.6) {
+            DailyMode::Obstacles
+        } else {
+            DailyMode::OpenArena
+        };
+
+        // Steady and Frenzy contradict each other, so at most one of them
+        let mut modifiers = Vec::new();
+        if rng.chance(0.4) {
+            modifiers.push(Modifier::QuickStart);
+        }
+        match rng.below(4) {
+            0 => modifiers.push(Modifier::Steady),
+            1 => modifiers.push(Modifier::Frenzy),
+            _ => {}
+        }
+
+        let mut gameplay = GameplayConfig {
+            initial_speed_ms: INITIAL_SPEED_MS,
+            min_speed_ms: MIN_SPEED_MS,
+            speed_increase_per_food: SPEED_INCREASE_PER_FOOD,
+            grid_size: None,
+            grid_width: Some(width),
+            grid_height: Some(height),
+            rules: RulesConfig::default(),
+        };
+        for modifier in &modifiers {
+            modifier.apply(&mut gameplay);
+        }
+
+        let walls = match mode {
+            DailyMode::Obstacles => obstacles(&mut rng, width as i32, height as i32),
+            DailyMode::OpenArena => Vec::new(),
+        };
+
+        DailyChallenge {
+            date: date.to_string(),
+            seed,
+            mode,
+            modifiers,
+            gameplay,
+            walls,
+        }
+    }
+
+    /// Where the scored attempt's replay is kept
+    pub fn replay_path(&self) -> PathBuf {
+        PathBuf::from(REPLAYS_DIR).join(format!("daily-{}.json", self.date))
+    }
+}
+
+/// Scattered horizontal and vertical wall segments, 2 to 5 cells long
+fn obstacles(rng: &mut SplitMix64, width: i32, height: i32) -> Vec<Position> {
+    let mut walls: Vec<Position> = Vec::new();
+    for _ in 0..4 + rng.below(7) {
+        let length = 2 + rng.below(4) as i32;
+        let horizontal = rng.chance(0.5);
+        let x = rng.below(width as u32) as i32;
+        let y = rng.below(height as u32) as i32;
+        for i in 0..length {
+            let cell = if horizontal {
+                Position { x: x + i, y }
+            } else {
+                Position { x, y: y + i }
+            };
+            if !walls.contains(&cell) {
+                walls.push(cell);
+            }
+        }
+    }
+    walls
+}
+
+/// Today's UTC date as `YYYY-MM-DD`
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / 86_400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Calendar date for a count of days since 1970-01-01 (proleptic Gregorian)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// SplitMix64 (Steele, Lea and Flood, 2014): a tiny generator whose output is fixed by its definition
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, by multiply-and-shift so no division is involved
+    pub(crate) fn below(&mut self, n: u32) -> u32 {
+        (((self.next_u64() >> 32) * n as u64) >> 32) as u32
+    }
+
+    /// True with probability `p`
+    fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+}
+
+/// FNV-1a: a tiny hash that is the same on every platform and Rust version
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitmix64_matches_the_reference_output() {
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn every_daily_board_is_valid() {
+        for day in 1..=28 {
+            let challenge = DailyChallenge::for_date(&format!("2026-02-{:02}", day));
+            assert_eq!(challenge.gameplay.validate(), Ok(()), "{}", challenge.date);
+        }
+    }
+
+    #[test]
+    fn same_date_builds_the_same_board() {
+        let a = DailyChallenge::for_date("2026-10-19");
+        let b = DailyChallenge::for_date("2026-10-19");
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(a.modifiers, b.modifiers);
+        assert_eq!(a.walls, b.walls);
+        assert_eq!(a.gameplay, b.gameplay);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::daily::DailyChallenge;
+use crate::locale::Locale;
+use crate::persistence::{DailyHistory, DailyResult};
+
+/// How many earlier days the daily screen lists
+const HISTORY_SHOWN: usize = 5;
+
+pub struct DailyState {
+    pub challenge: DailyChallenge,
+    pub history: DailyHistory,
+}
+
+impl DailyState {
+    pub fn new() -> Self {
+        DailyState {
+            challenge: DailyChallenge::today(),
+            history: DailyHistory::load(),
+        }
+    }
+
+    /// Pick up a new day and any attempts saved since
+    pub fn refresh(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Today's board and rules as lines of text
+    pub fn rules(&self, locale: &Locale) -> Vec<String> {
+        let challenge = &self.challenge;
+        let modifiers = if challenge.modifiers.is_empty() {
+            locale.get("daily.no_modifiers").to_string()
+        } else {
+            challenge
+                .modifiers
+                .iter()
+                .map(|m| locale.get(m.label_key()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        vec![
+            locale.format(
+                "daily.grid",
+                &[
+                    ("width", challenge.gameplay.grid_width().to_string()),
+                    ("height", challenge.gameplay.grid_height().to_string()),
+                ],
+            ),
+            locale.format("daily.mode", &[("mode", locale.get(challenge.mode.label_key()).to_string())]),
+            locale.format("daily.modifiers", &[("modifiers", modifiers)]),
+        ]
+    }
+
+    /// Whether today's scored attempt is still available, or what it scored
+    pub fn status(&self, locale: &Locale) -> String {
+        match self.today() {
+            Some(result) => locale.format("daily.played", &[("score", result.score.to_string())]),
+            None => locale.get("daily.ready").to_string(),
+        }
+    }
+
+    /// Today's result, once the scored attempt has been used
+    pub fn today(&self) -> Option<&DailyResult> {
+        self.history.result(&self.challenge.date)
+    }
+
+    /// Earlier days' results, newest first
+    pub fn previous(&self) -> impl Iterator<Item = &DailyResult> {
+        self.history
+            .results
+            .iter()
+            .rev()
+            .filter(|r| r.date != self.challenge.date)
+            .take(HISTORY_SHOWN)
+    }
+
+    /// Today's challenge and whether this attempt is scored, using up the scored attempt
+    pub fn start(&mut self) -> (DailyChallenge, bool) {
+        let scored = self.today().is_none();
+        if scored {
+            self.history.start_attempt(&self.challenge.date);
+            if let Err(e) = self.history.save() {
+                eprintln!("Failed to save daily history: {}", e);
+            }
+        }
+        (self.challenge.clone(), scored)
+    }
+}
@@ -0,0 +1,48 @@
+// Display modes - windowed, borderless fullscreen and exclusive fullscreen
+use crate::settings_state::next_in;
+use sdl2::video::{FullscreenType, Window};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    /// Fullscreen at the desktop resolution without changing video mode
+    Borderless,
+    /// Exclusive fullscreen at the window's size
+    Fullscreen,
+}
+
+impl DisplayMode {
+    const ALL: [DisplayMode; 3] = [DisplayMode::Windowed, DisplayMode::Borderless, DisplayMode::Fullscreen];
+
+    /// Catalog key of the mode's display name
+    pub fn label_key(self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "display.windowed",
+            DisplayMode::Borderless => "display.borderless",
+            DisplayMode::Fullscreen => "display.fullscreen",
+        }
+    }
+
+    pub fn step(self, forward: bool) -> Self {
+        next_in(&Self::ALL, self, forward)
+    }
+
+    /// Mode the fullscreen hotkey switches to from this one
+    pub fn toggled(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless | DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn apply(self, window: &mut Window) -> Result<(), String> {
+        let fullscreen = match self {
+            DisplayMode::Windowed => FullscreenType::Off,
+            DisplayMode::Borderless => FullscreenType::Desktop,
+            DisplayMode::Fullscreen => FullscreenType::True,
+        };
+        window.set_fullscreen(fullscreen)
+    }
+}
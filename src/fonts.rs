@@ -1,34 +1,120 @@
 // Font system - manages SDL2_ttf context and fonts
+use crate::assets::AssetResolver;
 use crate::resources;
 use once_cell::sync::OnceCell;
 use sdl2::ttf::{Font, Sdl2TtfContext};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Global TTF context singleton
 static TTF_CONTEXT: OnceCell<Sdl2TtfContext> = OnceCell::new();
 
+/// Bumped for every FontSystem so caches can tell a reloaded font from the old one
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Relative text sizes, derived from the base point size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FontSize {
+    /// Hints and fine print
+    Small,
+    /// Menus, HUD and body text
+    Normal,
+    /// Screen titles
+    Large,
+}
+
+/// One typeface loaded at each text size
+struct SizedFonts {
+    small: Font<'static, 'static>,
+    normal: Font<'static, 'static>,
+    large: Font<'static, 'static>,
+}
+
+impl SizedFonts {
+    fn load(
+        point_size: u16,
+        load: impl Fn(u16) -> Result<Font<'static, 'static>, String>,
+    ) -> Result<Self, String> {
+        Ok(SizedFonts {
+            small: load(point_size.saturating_sub(8).max(8))?,
+            normal: load(point_size)?,
+            large: load(point_size * 2)?,
+        })
+    }
+
+    fn sized(&self, size: FontSize) -> &Font<'static, 'static> {
+        match size {
+            FontSize::Small => &self.small,
+            FontSize::Normal => &self.normal,
+            FontSize::Large => &self.large,
+        }
+    }
+}
+
 /// FontSystem - owns fonts loaded from the global TTF context
+///
+/// Characters the main font has no glyph for, such as Cyrillic in Press
+/// Start 2P, are drawn with the fallback font instead.
 pub struct FontSystem {
-    font: Font<'static, 'static>,
+    main: SizedFonts,
+    fallback: Option<SizedFonts>,
+    point_size: u16,
+    generation: u64,
 }
 
 impl FontSystem {
-    /// Initialize font system and load fonts
-    pub fn new() -> Result<Self, String> {
+    /// Initialize font system and load fonts around the given base point size
+    ///
+    /// Press Start 2P is drawn on an 8px grid, so the other sizes step by 8.
+    pub fn new(assets: &AssetResolver, point_size: u16) -> Result<Self, String> {
         // Initialize global TTF context if not already done
         TTF_CONTEXT
             .get_or_try_init(|| {
                 sdl2::ttf::init().map_err(|e| format!("SDL2_ttf init failed: {}", e))
             })?;
 
-        // Load font from global context
+        // Load fonts from global context
         let ttf_context = TTF_CONTEXT.get().unwrap();
-        let font = resources::load_main_font(ttf_context, 16)?;
+        let main = SizedFonts::load(point_size, |size| resources::load_main_font(ttf_context, assets, size))?;
+        let fallback = SizedFonts::load(point_size, |size| resources::load_fallback_font(ttf_context, assets, size))
+            .map_err(|e| eprintln!("No fallback font, some characters may not show: {}", e))
+            .ok();
 
-        Ok(FontSystem { font })
+        Ok(FontSystem {
+            main,
+            fallback,
+            point_size,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        })
     }
 
-    /// Get reference to the font
+    /// Get reference to the normal-sized font
     pub fn font(&self) -> &Font<'static, 'static> {
-        &self.font
+        &self.main.normal
+    }
+
+    /// Get reference to the font at the given size
+    pub fn sized(&self, size: FontSize) -> &Font<'static, 'static> {
+        self.main.sized(size)
+    }
+
+    /// Font to draw `ch` with: the main font if it has the glyph, otherwise the fallback
+    pub fn for_char(&self, size: FontSize, ch: char) -> &Font<'static, 'static> {
+        let main = self.main.sized(size);
+        match &self.fallback {
+            Some(fallback) if main.find_glyph(ch).is_none() && fallback.sized(size).find_glyph(ch).is_some() => {
+                fallback.sized(size)
+            }
+            _ => main,
+        }
+    }
+
+    /// Base point size the fonts were loaded around
+    pub fn point_size(&self) -> u16 {
+        self.point_size
+    }
+
+    /// Identifies this set of fonts for caches
+    pub fn generation(&self) -> u64 {
+        self.generation
     }
 }
@@ -1,8 +1,14 @@
-use crate::config::Config;
-use rand::RngExt;
+use crate::config::{Config, GameplayConfig, SpeedCurve};
+use crate::daily::{DailyChallenge, SplitMix64};
+use crate::occupancy::{Cell, OccupancyGrid};
+use crate::replay::{Replay, ReplayInput};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Every this many foods counts as a speed-up milestone
+const SPEED_MILESTONE_FOODS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Down,
@@ -21,94 +27,271 @@ impl Direction {
     }
 }
 
+/// What ended a game
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathCause {
+    /// The grid edge or a level wall
+    Wall,
+    /// The snake's own body
+    SelfCollision,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
+/// A daily challenge being played
+#[derive(Debug, Clone)]
+pub struct DailyRun {
+    pub challenge: DailyChallenge,
+    /// Only the day's first attempt counts; later ones are practice
+    pub scored: bool,
+}
+
 pub struct GameState {
     pub snake: VecDeque<Position>,
+    /// Cell the tail left on the last move, or `None` if the snake grew (or has not moved)
+    ///
+    /// With the body shifted by one cell per move, this is all that is needed
+    /// to know where every segment was before it.
+    vacated_tail: Option<Position>,
+    /// Whether the snake has moved this game, so there is a move to interpolate
+    has_moved: bool,
     pub direction: Direction,
-    pub next_direction: Option<Direction>, // Input buffer
+    /// Turns waiting for coming moves, oldest first; one is applied per move
+    input_queue: VecDeque<Direction>,
     pub food: Position,
+    pub walls: Vec<Position>,
     pub score: u32,
+    pub foods_eaten: u32,
+    /// Segments still to be added from food already eaten
+    pending_growth: u32,
     pub game_over: bool,
+    pub death: Option<DeathCause>,
+    /// The snake filled every free cell: the game ended in a win, not a death
+    pub cleared: bool,
     pub paused: bool,
     pub current_speed_ms: u32,
     pub interpolation_progress: f32, // 0.0 to 1.0 for smooth movement animation
-    grid_size: u32,
+    /// Game time played, the sum of every tick's length; pauses don't count
+    pub elapsed_ms: u64,
+    grid_width: u32,
+    grid_height: u32,
     config: Config,
+    /// Walls of the selected level, restored after a daily challenge
+    level_walls: Vec<Position>,
+    /// The daily challenge, while one is being played; its rules replace the configured ones
+    daily: Option<DailyRun>,
+    /// What fills each cell, kept in step with `snake` and `walls`
+    occupancy: OccupancyGrid,
+    /// Food placement comes only from this, so a seed replays a game exactly
+    rng: SplitMix64,
+    /// This game so far, for saving as a replay
+    recording: Replay,
 }
 
 impl GameState {
     pub fn new(config: Config) -> Self {
-        let grid_size = config.gameplay.grid_size;
-        let center = (grid_size / 2) as i32;
+        let grid_width = config.gameplay.grid_width();
+        let grid_height = config.gameplay.grid_height();
+        let snake = starting_snake(grid_width, grid_height, config.gameplay.rules.starting_length);
 
-        let mut snake = VecDeque::new();
-        snake.push_back(Position { x: center, y: center });
-        snake.push_back(Position { x: center - 1, y: center });
-        snake.push_back(Position { x: center - 2, y: center });
+        let seed = rand::random();
+        let recording = Replay::new(seed, config.gameplay.clone(), Vec::new());
 
         let mut game = GameState {
+            vacated_tail: None,
+            has_moved: false,
             snake,
             direction: Direction::Right,
-            next_direction: None,
+            input_queue: VecDeque::new(),
             food: Position { x: 0, y: 0 },
+            walls: Vec::new(),
             score: 0,
+            foods_eaten: 0,
+            pending_growth: 0,
             game_over: false,
+            death: None,
+            cleared: false,
             paused: false,
             current_speed_ms: config.gameplay.initial_speed_ms,
             interpolation_progress: 0.0,
-            grid_size,
+            elapsed_ms: 0,
+            grid_width,
+            grid_height,
             config,
+            level_walls: Vec::new(),
+            daily: None,
+            occupancy: OccupancyGrid::new(grid_width, grid_height),
+            rng: SplitMix64::new(seed),
+            recording,
         };
 
+        game.fill_occupancy();
         game.spawn_food();
         game
     }
 
+    /// A game set up to play back a replay: its rules, walls and seed
+    ///
+    /// Fails if the replay's rules would not pass in `config.toml`.
+    pub fn from_replay(mut config: Config, replay: &Replay) -> Result<Self, String> {
+        replay.gameplay.validate().map_err(|e| format!("Invalid replay: {}", e))?;
+        config.gameplay = replay.gameplay.clone();
+        let mut game = GameState::new(config);
+        game.walls = replay.walls.clone();
+        game.reset_with_seed(replay.seed);
+        Ok(game)
+    }
+
+    /// Start a new game with a fresh random seed, or replay the daily board as practice
     pub fn reset(&mut self) {
-        let center = (self.grid_size / 2) as i32;
+        match &mut self.daily {
+            Some(run) => {
+                run.scored = false;
+                let seed = run.challenge.seed;
+                self.reset_with_seed(seed);
+            }
+            None => self.reset_with_seed(rand::random()),
+        }
+    }
+
+    /// Play this game's seed again, racing its ghost; a daily challenge replays as practice
+    pub fn retry(&mut self) {
+        if let Some(run) = &mut self.daily {
+            run.scored = false;
+        }
+        self.reset_with_seed(self.recording.seed);
+    }
+
+    /// Start a daily challenge with its own grid, walls, speeds and seed
+    pub fn start_daily(&mut self, challenge: DailyChallenge, scored: bool) -> Result<(), String> {
+        challenge
+            .gameplay
+            .validate()
+            .map_err(|e| format!("Invalid daily challenge for {}: {}", challenge.date, e))?;
+        let seed = challenge.seed;
+        self.daily = Some(DailyRun { challenge, scored });
+        self.apply_rules();
+        self.reset_with_seed(seed);
+        Ok(())
+    }
+
+    /// Start a regular game, leaving any daily challenge
+    pub fn start_standard(&mut self) {
+        if self.daily.take().is_some() {
+            self.apply_rules();
+        }
+        self.reset();
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn daily(&self) -> Option<&DailyRun> {
+        self.daily.as_ref()
+    }
+
+    /// Rules in effect: the daily challenge's, or the configured ones
+    fn gameplay(&self) -> &GameplayConfig {
+        self.daily
+            .as_ref()
+            .map_or(&self.config.gameplay, |run| &run.challenge.gameplay)
+    }
 
-        self.snake.clear();
-        self.snake.push_back(Position { x: center, y: center });
-        self.snake.push_back(Position { x: center - 1, y: center });
-        self.snake.push_back(Position { x: center - 2, y: center });
+    /// Size the grid and lay out walls for the rules in effect
+    fn apply_rules(&mut self) {
+        self.grid_width = self.gameplay().grid_width();
+        self.grid_height = self.gameplay().grid_height();
+        let walls = match &self.daily {
+            Some(run) => run.challenge.walls.clone(),
+            None => self.level_walls.clone(),
+        };
+        self.walls = self.playable_walls(walls);
+    }
 
+    /// Start a new game whose food placement follows `seed`
+    pub fn reset_with_seed(&mut self, seed: u64) {
+        self.snake = starting_snake(self.grid_width, self.grid_height, self.gameplay().rules.starting_length);
+        self.vacated_tail = None;
+        self.has_moved = false;
         self.direction = Direction::Right;
-        self.next_direction = None;
+        self.input_queue.clear();
         self.score = 0;
+        self.foods_eaten = 0;
+        self.pending_growth = 0;
         self.game_over = false;
+        self.death = None;
+        self.cleared = false;
         self.paused = false;
-        self.current_speed_ms = self.config.gameplay.initial_speed_ms;
+        self.current_speed_ms = self.gameplay().initial_speed_ms;
         self.interpolation_progress = 0.0;
+        self.elapsed_ms = 0;
+        self.rng = SplitMix64::new(seed);
+        self.recording = Replay::new(seed, self.gameplay().clone(), self.walls.clone());
+        self.fill_occupancy();
         self.spawn_food();
     }
 
+    /// Replace the level's wall layout; a daily challenge in progress keeps its own walls
+    pub fn set_walls(&mut self, walls: Vec<Position>) {
+        self.level_walls = walls;
+        if self.daily.is_none() {
+            self.apply_rules();
+            self.reset();
+        }
+    }
+
+    /// `walls` without those outside the grid or in the snake's starting path
+    fn playable_walls(&self, walls: Vec<Position>) -> Vec<Position> {
+        let center_x = (self.grid_width / 2) as i32;
+        let center_y = (self.grid_height / 2) as i32;
+        let tail_x = center_x + 1 - self.gameplay().rules.starting_length as i32;
+        let (width, height) = (self.grid_width as i32, self.grid_height as i32);
+        walls
+            .into_iter()
+            .filter(|w| w.x >= 0 && w.x < width && w.y >= 0 && w.y < height)
+            .filter(|w| !(w.y == center_y && (tail_x..=center_x + 1).contains(&w.x)))
+            .collect()
+    }
+
+    /// Queue a turn for a coming move
+    ///
+    /// Each turn is checked against the one queued before it, or the current
+    /// heading if none is: repeats and reversals are dropped, as are turns once
+    /// the queue is full. Two quick turns therefore make a U-turn into the next lane.
     pub fn set_direction(&mut self, new_direction: Direction) {
-        // Prevent reversing into self
-        if new_direction != self.direction.opposite() {
-            // Input buffering: store next direction if different from current
-            if new_direction != self.direction {
-                self.next_direction = Some(new_direction);
-            }
+        let previous = self.input_queue.back().copied().unwrap_or(self.direction);
+        if new_direction == previous || new_direction == previous.opposite() {
+            return;
         }
+        if self.input_queue.len() < self.config.input.queue_size.max(1) {
+            self.input_queue.push_back(new_direction);
+        }
+    }
+
+    /// Turns still waiting to be applied, oldest first
+    pub fn queued_turns(&self) -> impl Iterator<Item = Direction> + '_ {
+        self.input_queue.iter().copied()
     }
 
     pub fn update(&mut self) -> bool {
-        if self.game_over || self.paused {
+        if self.finished() || self.paused {
             return false;
         }
 
-        // Reset interpolation for new movement step
-        self.interpolation_progress = 0.0;
+        let tick = self.recording.ticks;
+        self.recording.ticks += 1;
+        self.elapsed_ms += self.current_speed_ms as u64;
 
-        // Apply buffered input
-        if let Some(next_dir) = self.next_direction.take() {
+        // Apply the oldest queued turn
+        if let Some(next_dir) = self.input_queue.pop_front() {
             if next_dir != self.direction.opposite() {
                 self.direction = next_dir;
+                self.recording.inputs.push(ReplayInput { tick, direction: next_dir });
             }
         }
 
@@ -120,87 +303,257 @@ impl GameState {
             Direction::Right => Position { x: head.x + 1, y: head.y },
         };
 
-        // Check wall collision
-        if new_head.x < 0 || new_head.x >= self.grid_size as i32
-            || new_head.y < 0 || new_head.y >= self.grid_size as i32 {
-            self.game_over = true;
-            return false;
-        }
+        // Food grows the snake by the rules' amount, one segment per move
+        let ate = new_head == self.food;
+        let growth = if ate { self.gameplay().rules.growth_per_food } else { 0 };
+        let grows = self.pending_growth + growth > 0;
 
-        // Check self collision
-        if self.snake.contains(&new_head) {
+        // Check collision with the grid edge, level walls and the body; with
+        // tail chasing, the cell the tail leaves on this move counts as free
+        let tail_leaves = self.gameplay().rules.tail_chase && !grows && self.snake.back() == Some(&new_head);
+        let cell = if tail_leaves { Cell::Empty } else { self.occupancy.get(new_head) };
+        let death = match cell {
+            Cell::Wall => Some(DeathCause::Wall),
+            Cell::Snake => Some(DeathCause::SelfCollision),
+            Cell::Empty => None,
+        };
+        if death.is_some() {
             self.game_over = true;
+            self.death = death;
             return false;
         }
 
+        // Restart interpolation towards the new body
+        self.has_moved = true;
+        self.interpolation_progress = 0.0;
+
+        // The tail moves off first, so a chased tail frees its cell for the head
+        self.vacated_tail = None;
+        if grows {
+            self.pending_growth = self.pending_growth + growth - 1;
+        } else if let Some(tail) = self.snake.pop_back() {
+            self.occupancy.set(tail, Cell::Empty);
+            self.vacated_tail = Some(tail);
+        }
         self.snake.push_front(new_head);
+        self.occupancy.set(new_head, Cell::Snake);
 
-        // Check food collision
-        if new_head == self.food {
-            self.score += 10;
-            self.spawn_food();
+        if ate {
+            let rules = &self.gameplay().rules;
+            let points = if rules.score_by_length { self.snake.len() as u32 } else { rules.score_per_food };
+            self.score += points;
+            self.foods_eaten += 1;
+            self.recording.score = self.score;
+            if !self.spawn_food() {
+                // Nowhere left to put food: the board is cleared
+                self.cleared = true;
+                return true;
+            }
             self.increase_speed();
             return true; // Food eaten
         } else {
-            self.snake.pop_back();
             return false;
         }
     }
 
-    fn spawn_food(&mut self) {
-        let mut rng = rand::rng();
-        loop {
-            let pos = Position {
-                x: rng.random_range(0..self.grid_size as i32),
-                y: rng.random_range(0..self.grid_size as i32),
-            };
-            if !self.snake.contains(&pos) {
-                self.food = pos;
-                break;
-            }
+    /// Put food on a free cell picked uniformly at random; false if there is none
+    ///
+    /// Picks from the occupancy grid's free cells directly, in constant time however full the board is.
+    fn spawn_food(&mut self) -> bool {
+        let free = self.occupancy.free_count();
+        if free == 0 {
+            return false;
+        }
+        let pick = self.rng.below(free as u32) as usize;
+        self.food = self.occupancy.free_cell(pick);
+        true
+    }
+
+    /// Rebuild the occupancy grid from scratch for the current grid, walls and snake
+    fn fill_occupancy(&mut self) {
+        self.occupancy = OccupancyGrid::new(self.grid_width, self.grid_height);
+        for wall in &self.walls {
+            self.occupancy.set(*wall, Cell::Wall);
+        }
+        for segment in &self.snake {
+            self.occupancy.set(*segment, Cell::Snake);
         }
     }
 
     fn increase_speed(&mut self) {
-        let decrease = self.config.gameplay.speed_increase_per_food;
-        let min_speed = self.config.gameplay.min_speed_ms;
+        let gameplay = self.gameplay();
+        let per_food = gameplay.speed_increase_per_food;
+        let min_speed = gameplay.min_speed_ms;
+        let decrease = match gameplay.rules.speed_curve {
+            SpeedCurve::Linear => per_food,
+            // Integer maths so replays play out the same everywhere; never stalls above the minimum
+            SpeedCurve::Exponential => {
+                let fraction = self.current_speed_ms as u64 * per_food as u64 / gameplay.initial_speed_ms.max(1) as u64;
+                (fraction as u32).max(per_food.min(1))
+            }
+            SpeedCurve::Stepped if self.reached_speed_milestone() => per_food * SPEED_MILESTONE_FOODS,
+            SpeedCurve::Stepped => 0,
+        };
 
         if self.current_speed_ms > min_speed {
             self.current_speed_ms = self.current_speed_ms.saturating_sub(decrease).max(min_speed);
         }
     }
 
+    /// Whether the snake has sped up as far as it can
+    pub fn at_min_speed(&self) -> bool {
+        self.current_speed_ms <= self.gameplay().min_speed_ms
+    }
+
+    /// True right after the food that completes a speed-up milestone
+    pub fn reached_speed_milestone(&self) -> bool {
+        self.foods_eaten > 0 && self.foods_eaten.is_multiple_of(SPEED_MILESTONE_FOODS)
+    }
+
+    /// The game so far as a replay
+    pub fn replay(&self) -> &Replay {
+        &self.recording
+    }
+
+    /// What fills each cell of the grid
+    pub fn occupancy(&self) -> &OccupancyGrid {
+        &self.occupancy
+    }
+
+    /// Number of moves made so far
+    pub fn tick(&self) -> u32 {
+        self.recording.ticks
+    }
+
+    /// Grid dimensions as (width, height) in cells
+    pub fn grid_dimensions(&self) -> (u32, u32) {
+        (self.grid_width, self.grid_height)
+    }
+
+    /// Whether the game has ended, by a death or a cleared board
+    pub fn finished(&self) -> bool {
+        self.game_over || self.cleared
+    }
+
     pub fn toggle_pause(&mut self) {
         self.paused = !self.paused;
     }
 
     /// Update interpolation progress for smooth movement animation
     pub fn update_interpolation(&mut self, elapsed_ms: u64) {
-        if !self.game_over && !self.paused {
+        if !self.finished() && !self.paused {
             self.interpolation_progress = (elapsed_ms as f32 / self.current_speed_ms as f32).min(1.0);
         }
     }
 
-    /// Get interpolated head position for smooth rendering
-    pub fn get_interpolated_head(&self) -> (f32, f32) {
-        if self.snake.is_empty() {
-            return (0.0, 0.0);
+    /// Where the tail was before the last move, if it has moved off that cell
+    pub fn previous_tail(&self) -> Option<Position> {
+        self.vacated_tail
+    }
+
+    /// Interpolated position of every segment, head first, for smooth rendering
+    ///
+    /// Segment `i` slides from where segment `i` was before the last move, so
+    /// each one travels exactly one cell along the body's path and corners are
+    /// followed rather than cut. That earlier cell is where segment `i + 1` is
+    /// now, or the vacated tail cell for the last segment. A segment added by
+    /// growing has no earlier position and holds still on the old tail cell.
+    pub fn interpolated_body(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let t = self.interpolation_progress;
+        self.snake.iter().enumerate().map(move |(i, current)| {
+            let previous = if self.has_moved {
+                self.snake.get(i + 1).or(self.vacated_tail.as_ref()).unwrap_or(current)
+            } else {
+                current
+            };
+            (
+                previous.x as f32 + (current.x - previous.x) as f32 * t,
+                previous.y as f32 + (current.y - previous.y) as f32 * t,
+            )
+        })
+    }
+}
+
+/// A snake of `length` segments heading right from the middle of the grid, head first
+fn starting_snake(grid_width: u32, grid_height: u32, length: u32) -> VecDeque<Position> {
+    let center_x = (grid_width / 2) as i32;
+    let center_y = (grid_height / 2) as i32;
+    (0..length.max(1) as i32)
+        .map(|i| Position { x: center_x - i, y: center_y })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_CONFIG;
+
+    /// A game with its food parked in a corner, away from the snake's path
+    fn game() -> GameState {
+        let mut game = GameState::new(toml::from_str(DEFAULT_CONFIG).expect("built-in config parses"));
+        game.reset_with_seed(1);
+        game.food = Position { x: 0, y: 0 };
+        game
+    }
+
+    #[test]
+    fn malformed_replay_is_rejected() {
+        let config: Config = toml::from_str(DEFAULT_CONFIG).expect("built-in config parses");
+        let mut gameplay = config.gameplay.clone();
+        gameplay.grid_size = None;
+        gameplay.grid_width = None;
+        gameplay.grid_height = None;
+        let replay = Replay::new(1, gameplay, Vec::new());
+        assert!(GameState::from_replay(config, &replay).is_err());
+    }
+
+    #[test]
+    fn food_lands_on_the_same_cells_for_a_seed() {
+        let mut game = game();
+        game.reset_with_seed(7);
+        let mut foods = vec![game.food];
+        for _ in 0..2 {
+            game.spawn_food();
+            foods.push(game.food);
         }
+        let expected = [(19, 11), (15, 0), (27, 26)].map(|(x, y)| Position { x, y });
+        assert_eq!(foods, expected);
+    }
 
-        let current_head = self.snake.front().unwrap();
+    #[test]
+    fn body_holds_still_before_the_first_move() {
+        let game = game();
+        let body: Vec<(f32, f32)> = game.interpolated_body().collect();
+        let cells: Vec<(f32, f32)> = game.snake.iter().map(|p| (p.x as f32, p.y as f32)).collect();
+        assert_eq!(body, cells);
+    }
 
-        // Calculate previous head position based on direction
-        let (prev_x, prev_y) = match self.direction {
-            Direction::Up => (current_head.x, current_head.y + 1),
-            Direction::Down => (current_head.x, current_head.y - 1),
-            Direction::Left => (current_head.x + 1, current_head.y),
-            Direction::Right => (current_head.x - 1, current_head.y),
-        };
+    #[test]
+    fn every_segment_slides_one_cell_along_the_body() {
+        let mut game = game();
+        let before: Vec<Position> = game.snake.iter().copied().collect();
+        game.update();
+        game.interpolation_progress = 0.5;
+
+        let body: Vec<(f32, f32)> = game.interpolated_body().collect();
+        assert_eq!(body.len(), before.len());
+        for ((x, y), was) in body.iter().zip(&before) {
+            assert_eq!((*x, *y), (was.x as f32 + 0.5, was.y as f32));
+        }
+        assert_eq!(game.previous_tail(), before.last().copied());
+    }
 
-        // Linear interpolation
-        let interp_x = prev_x as f32 + (current_head.x - prev_x) as f32 * self.interpolation_progress;
-        let interp_y = prev_y as f32 + (current_head.y - prev_y) as f32 * self.interpolation_progress;
+    #[test]
+    fn grown_segment_holds_on_the_old_tail() {
+        let mut game = game();
+        let old_tail = *game.snake.back().unwrap();
+        let head = *game.snake.front().unwrap();
+        game.food = Position { x: head.x + 1, y: head.y };
+        game.update();
+        game.interpolation_progress = 0.5;
 
-        (interp_x, interp_y)
+        let last = game.interpolated_body().last().unwrap();
+        assert_eq!(last, (old_tail.x as f32, old_tail.y as f32));
+        assert_eq!(game.previous_tail(), None);
     }
 }
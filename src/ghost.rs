@@ -0,0 +1,53 @@
+// Ghost - the best earlier run on the same seed, played back alongside the player
+use crate::game_state::GameState;
+use crate::replay::Replay;
+
+pub struct Ghost {
+    game: GameState,
+    replay: Replay,
+}
+
+impl Ghost {
+    /// The best saved run on `player`'s seed and rules, if there is one
+    pub fn load(player: &GameState) -> Option<Self> {
+        let current = player.replay();
+        let replay = Replay::load(&Replay::ghost_path(current.seed)).ok()?;
+        if replay.gameplay != current.gameplay || replay.walls != current.walls {
+            return None;
+        }
+        let game = GameState::from_replay(player.config().clone(), &replay)
+            .map_err(|e| eprintln!("Ignoring ghost for seed {:016x}: {}", current.seed, e))
+            .ok()?;
+        Some(Ghost { game, replay })
+    }
+
+    /// Advance the ghost until it has played as long as `player`, so both move in step
+    ///
+    /// The ghost is its own game: it never touches the player's snake or food.
+    pub fn sync(&mut self, player: &GameState) {
+        while !self.game.finished()
+            && self.game.tick() < self.replay.ticks
+            && self.game.elapsed_ms + self.game.current_speed_ms as u64 <= player.elapsed_ms
+        {
+            if let Some(direction) = self.replay.input_at(self.game.tick()) {
+                self.game.set_direction(direction);
+            }
+            self.game.update();
+        }
+        self.game.interpolation_progress = player.interpolation_progress;
+    }
+
+    pub fn game(&self) -> &GameState {
+        &self.game
+    }
+
+    /// Whether the ghost's run has ended
+    pub fn finished(&self) -> bool {
+        self.game.finished() || self.game.tick() >= self.replay.ticks
+    }
+
+    /// Player's score minus the ghost's at the same moment
+    pub fn score_delta(&self, player: &GameState) -> i64 {
+        player.score as i64 - self.game.score as i64
+    }
+}
@@ -10,6 +10,8 @@ pub enum GameInput {
     Select,
     Pause,
     Back,
+    /// Play the finished game's seed again
+    Retry,
     Quit,
 }
 
@@ -21,6 +23,7 @@ pub fn keycode_to_input(keycode: Keycode) -> Option<GameInput> {
         Keycode::Right | Keycode::D => Some(GameInput::Right),
         Keycode::Return | Keycode::Space => Some(GameInput::Select),
         Keycode::Escape => Some(GameInput::Back),
+        Keycode::R => Some(GameInput::Retry),
         _ => None,
     }
 }
@@ -33,6 +36,7 @@ pub fn button_to_input(button: Button) -> Option<GameInput> {
         Button::DPadRight => Some(GameInput::Right),
         Button::A => Some(GameInput::Select),
         Button::B => Some(GameInput::Back),
+        Button::Y => Some(GameInput::Retry),
         Button::Start => Some(GameInput::Pause),
         _ => None,
     }
@@ -1,78 +1,142 @@
-use crate::audio::AudioSystem;
+use crate::achievements_state::AchievementsState;
+use crate::daily_state::DailyState;
 use crate::game_state::{Direction, GameState};
 use crate::input::GameInput;
 use crate::leaderboard_state::LeaderboardState;
 use crate::menu_state::{MenuItem, MenuState};
+use crate::persistence;
+use crate::settings_state::{SettingsItem, SettingsState};
+use crate::stats_state::StatsState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     MainMenu,
     Playing,
+    /// The board was cleared: the win screen over the filled playfield
+    Cleared,
+    Daily,
     Leaderboard,
+    Stats,
+    Achievements,
+    Settings,
+    Credits,
 }
 
 pub struct InputHandler;
 
 impl InputHandler {
+    /// Apply one input to the current screen, calling `play_click` for menu feedback
+    ///
+    /// Returns true when the player chose to quit.
+    #[allow(clippy::too_many_arguments)]
     pub fn handle_input(
         app_state: &mut AppState,
         menu_state: &mut MenuState,
         game_state: &mut GameState,
         leaderboard_state: &mut LeaderboardState,
+        daily_state: &mut DailyState,
+        stats_state: &mut StatsState,
+        achievements_state: &mut AchievementsState,
+        settings_state: &mut SettingsState,
         input: GameInput,
-        audio: &AudioSystem,
-    ) {
+        play_click: &dyn Fn(),
+    ) -> bool {
         match *app_state {
             AppState::MainMenu => {
-                Self::handle_menu_input(app_state, menu_state, game_state, leaderboard_state, input, audio);
+                return Self::handle_menu_input(
+                    app_state,
+                    menu_state,
+                    game_state,
+                    leaderboard_state,
+                    daily_state,
+                    stats_state,
+                    achievements_state,
+                    input,
+                    play_click,
+                );
             }
             AppState::Playing => {
                 Self::handle_game_input(app_state, game_state, input);
             }
+            AppState::Cleared => {
+                Self::handle_cleared_input(app_state, game_state, input);
+            }
             AppState::Leaderboard => {
                 Self::handle_leaderboard_input(app_state, input);
             }
+            AppState::Daily => {
+                Self::handle_daily_input(app_state, daily_state, game_state, input, play_click);
+            }
+            AppState::Stats => {
+                Self::handle_stats_input(app_state, input);
+            }
+            AppState::Achievements => {
+                Self::handle_achievements_input(app_state, achievements_state, input, play_click);
+            }
+            AppState::Settings => {
+                Self::handle_settings_input(app_state, settings_state, input, play_click);
+            }
+            AppState::Credits => {
+                Self::handle_credits_input(app_state, input);
+            }
         }
+        false
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_menu_input(
         app_state: &mut AppState,
         menu_state: &mut MenuState,
         game_state: &mut GameState,
         leaderboard_state: &mut LeaderboardState,
+        daily_state: &mut DailyState,
+        stats_state: &mut StatsState,
+        achievements_state: &mut AchievementsState,
         input: GameInput,
-        audio: &AudioSystem,
-    ) {
+        play_click: &dyn Fn(),
+    ) -> bool {
         match input {
             GameInput::Up => {
                 menu_state.move_up();
-                audio.play_click();
+                play_click();
             }
             GameInput::Down => {
                 menu_state.move_down();
-                audio.play_click();
+                play_click();
             }
             GameInput::Select => {
-                audio.play_click();
+                play_click();
                 match menu_state.selected_item {
                     MenuItem::Start => {
-                        game_state.reset();
+                        game_state.start_standard();
                         *app_state = AppState::Playing;
                     }
+                    MenuItem::Daily => {
+                        daily_state.refresh();
+                        *app_state = AppState::Daily;
+                    }
                     MenuItem::Leaderboard => {
                         leaderboard_state.refresh();
                         *app_state = AppState::Leaderboard;
                     }
-                    MenuItem::Exit => {
-                        std::process::exit(0);
+                    MenuItem::Stats => {
+                        stats_state.refresh();
+                        *app_state = AppState::Stats;
+                    }
+                    MenuItem::Achievements => {
+                        achievements_state.refresh();
+                        *app_state = AppState::Achievements;
                     }
+                    MenuItem::Settings => {
+                        *app_state = AppState::Settings;
+                    }
+                    MenuItem::Exit => return true,
                 }
             }
-            GameInput::Back => {
-                std::process::exit(0);
-            }
+            GameInput::Back => return true,
             _ => {}
         }
+        false
     }
 
     fn handle_game_input(app_state: &mut AppState, game_state: &mut GameState, input: GameInput) {
@@ -84,6 +148,10 @@ impl InputHandler {
                 GameInput::Select => {
                     game_state.reset();
                 }
+                GameInput::Retry => {
+                    persistence::save_ghost(game_state);
+                    game_state.retry();
+                }
                 _ => {}
             }
         } else {
@@ -100,9 +168,122 @@ impl InputHandler {
         }
     }
 
+    fn handle_cleared_input(app_state: &mut AppState, game_state: &mut GameState, input: GameInput) {
+        match input {
+            GameInput::Back => {
+                *app_state = AppState::MainMenu;
+            }
+            GameInput::Select => {
+                game_state.reset();
+                *app_state = AppState::Playing;
+            }
+            GameInput::Retry => {
+                persistence::save_ghost(game_state);
+                game_state.retry();
+                *app_state = AppState::Playing;
+            }
+            _ => {}
+        }
+    }
+
     fn handle_leaderboard_input(app_state: &mut AppState, input: GameInput) {
         if input == GameInput::Back {
             *app_state = AppState::MainMenu;
         }
     }
+
+    fn handle_daily_input(
+        app_state: &mut AppState,
+        daily_state: &mut DailyState,
+        game_state: &mut GameState,
+        input: GameInput,
+        play_click: &dyn Fn(),
+    ) {
+        match input {
+            GameInput::Select => {
+                play_click();
+                let (challenge, scored) = daily_state.start();
+                match game_state.start_daily(challenge, scored) {
+                    Ok(()) => *app_state = AppState::Playing,
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            GameInput::Back => {
+                *app_state = AppState::MainMenu;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_stats_input(app_state: &mut AppState, input: GameInput) {
+        if input == GameInput::Back {
+            *app_state = AppState::MainMenu;
+        }
+    }
+
+    fn handle_achievements_input(
+        app_state: &mut AppState,
+        achievements_state: &mut AchievementsState,
+        input: GameInput,
+        play_click: &dyn Fn(),
+    ) {
+        match input {
+            GameInput::Up => {
+                achievements_state.move_up();
+                play_click();
+            }
+            GameInput::Down => {
+                achievements_state.move_down();
+                play_click();
+            }
+            GameInput::Back => {
+                *app_state = AppState::MainMenu;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_settings_input(
+        app_state: &mut AppState,
+        settings_state: &mut SettingsState,
+        input: GameInput,
+        play_click: &dyn Fn(),
+    ) {
+        match input {
+            GameInput::Up => {
+                settings_state.move_up();
+                play_click();
+            }
+            GameInput::Down => {
+                settings_state.move_down();
+                play_click();
+            }
+            GameInput::Left => {
+                settings_state.adjust(false);
+                play_click();
+            }
+            GameInput::Right => {
+                settings_state.adjust(true);
+                play_click();
+            }
+            GameInput::Select => {
+                play_click();
+                match settings_state.selected_item {
+                    SettingsItem::Credits => *app_state = AppState::Credits,
+                    SettingsItem::Back => *app_state = AppState::MainMenu,
+                    _ => settings_state.adjust(true),
+                }
+            }
+            GameInput::Back => {
+                *app_state = AppState::MainMenu;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_credits_input(app_state: &mut AppState, input: GameInput) {
+        if input == GameInput::Back || input == GameInput::Select {
+            *app_state = AppState::Settings;
+        }
+    }
 }
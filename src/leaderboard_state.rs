@@ -1,4 +1,6 @@
-use crate::persistence::Leaderboard;
+use crate::config::{RulesConfig, SpeedCurve};
+use crate::locale::Locale;
+use crate::persistence::{Leaderboard, ScoreEntry};
 
 pub struct LeaderboardState {
     pub leaderboard: Leaderboard,
@@ -14,4 +16,48 @@ impl LeaderboardState {
     pub fn refresh(&mut self) {
         self.leaderboard = Leaderboard::load();
     }
+
+    /// Text of the entry at `rank` (from 1), noting a cleared board and any non-classic rules
+    pub fn entry_text(entry: &ScoreEntry, rank: usize, locale: &Locale) -> String {
+        let key = if entry.cleared { "leaderboard.entry_cleared" } else { "leaderboard.entry" };
+        let text = locale.format(
+            key,
+            &[
+                ("rank", rank.to_string()),
+                ("name", entry.name.clone()),
+                ("score", entry.score.to_string()),
+            ],
+        );
+        match rules_label(&entry.rules, locale) {
+            Some(rules) => locale.format("leaderboard.with_rules", &[("entry", text), ("rules", rules)]),
+            None => text,
+        }
+    }
+}
+
+/// Short description of how `rules` differ from the classic game, or `None` if they don't
+fn rules_label(rules: &RulesConfig, locale: &Locale) -> Option<String> {
+    let classic = RulesConfig::default();
+    let value = |key: &str, value: u32| locale.format(key, &[("value", value.to_string())]);
+    let mut parts = Vec::new();
+    if rules.tail_chase != classic.tail_chase {
+        parts.push(locale.get("rules.tail_chase").to_string());
+    }
+    if rules.growth_per_food != classic.growth_per_food {
+        parts.push(value("rules.growth", rules.growth_per_food));
+    }
+    if rules.starting_length != classic.starting_length {
+        parts.push(value("rules.starting_length", rules.starting_length));
+    }
+    if rules.score_by_length {
+        parts.push(locale.get("rules.score_by_length").to_string());
+    } else if rules.score_per_food != classic.score_per_food {
+        parts.push(value("rules.score_per_food", rules.score_per_food));
+    }
+    match rules.speed_curve {
+        SpeedCurve::Linear => {}
+        SpeedCurve::Exponential => parts.push(locale.get("rules.exponential").to_string()),
+        SpeedCurve::Stepped => parts.push(locale.get("rules.stepped").to_string()),
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
 }
@@ -0,0 +1,69 @@
+// Level layouts - wall maps loaded from asset packs
+use crate::asset_pack::AssetPack;
+use crate::game_state::Position;
+use crate::persistence::{LEVEL_MODE_PREFIX, OPEN_ARENA_MODE};
+
+/// Level - a named set of wall cells
+///
+/// Levels are plain text: `#` marks a wall, any other character is open floor.
+/// Row 0 is the top of the grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub walls: Vec<Position>,
+}
+
+impl Level {
+    pub fn parse(name: &str, text: &str) -> Result<Self, String> {
+        let walls: Vec<Position> = text
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| Position { x: x as i32, y: y as i32 })
+            })
+            .collect();
+
+        if walls.is_empty() {
+            return Err(format!("Level '{}' has no walls", name));
+        }
+
+        Ok(Level {
+            name: name.to_string(),
+            walls,
+        })
+    }
+}
+
+/// Walls of the selected level in the active pack, or none for the open arena
+pub fn selected_walls(level: Option<&str>, pack: Option<&AssetPack>) -> Vec<Position> {
+    let (Some(path), Some(pack)) = (level, pack) else {
+        return Vec::new();
+    };
+    pack.read(path)
+        .and_then(|bytes| Level::parse(&level_name(path), &String::from_utf8_lossy(&bytes)))
+        .map_or_else(
+            |e| {
+                eprintln!("Failed to load level {}: {}", path, e);
+                Vec::new()
+            },
+            |level| level.walls,
+        )
+}
+
+/// Name the profile keeps best scores under: the selected level, or the open arena
+pub fn mode_name(level: Option<&str>) -> String {
+    level.map_or_else(
+        || OPEN_ARENA_MODE.to_string(),
+        |path| format!("{}{}", LEVEL_MODE_PREFIX, level_name(path)),
+    )
+}
+
+/// Display name for a level file: its file name without extension
+pub fn level_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_stem()
+        .map_or_else(|| path.to_string(), |s| s.to_string_lossy().into_owned())
+}
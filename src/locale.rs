@@ -0,0 +1,150 @@
+// Localization - UI strings loaded from per-language TOML catalogs
+use crate::assets::AssetResolver;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Directory (inside an assets root) that holds extra or replacement catalogs
+pub const LOCALES_DIR: &str = "locales";
+
+/// Language every other catalog falls back to
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Catalogs compiled into the binary, keyed by language code
+const BUILTIN_LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../assets/locales/en.toml")),
+    ("uk", include_str!("../assets/locales/uk.toml")),
+];
+
+/// Locale - every UI string in one language
+///
+/// Catalogs are TOML with a `name` and tables of strings; nested keys are
+/// joined with dots, so `[menu] start = "START"` is looked up as `menu.start`.
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    /// Language code, taken from the catalog's file name
+    pub id: String,
+    /// Language name in that language
+    pub name: String,
+    strings: HashMap<String, String>,
+}
+
+impl Locale {
+    pub fn parse(id: &str, text: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(text).map_err(|e| format!("Invalid catalog '{}': {}", id, e))?;
+
+        let mut locale = Locale {
+            id: id.to_string(),
+            name: id.to_string(),
+            strings: HashMap::new(),
+        };
+        for (key, value) in table {
+            match value {
+                toml::Value::String(name) if key == "name" => locale.name = name,
+                value => flatten(&key, value, &mut locale.strings),
+            }
+        }
+        Ok(locale)
+    }
+
+    fn load_file(path: &Path) -> Result<Self, String> {
+        let id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .ok_or_else(|| format!("Invalid catalog path: {:?}", path))?;
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        Self::parse(&id, &text)
+    }
+
+    /// String for `key`, or the key itself if no catalog has it
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map_or(key, String::as_str)
+    }
+
+    /// String for `key`, if this catalog has it
+    pub fn lookup<'a>(&'a self, key: &str) -> Option<&'a str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// String for `key` with each `{name}` replaced by its value from `args`
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    /// Fill in any strings this catalog is missing from `fallback`
+    fn with_fallback(mut self, fallback: &Locale) -> Self {
+        for (key, value) in &fallback.strings {
+            self.strings.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        self
+    }
+}
+
+/// Add every string in `value` to `strings`, keyed by its dotted path
+fn flatten(prefix: &str, value: toml::Value, strings: &mut HashMap<String, String>) {
+    match value {
+        toml::Value::String(text) => {
+            strings.insert(prefix.to_string(), text);
+        }
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                flatten(&format!("{}.{}", prefix, key), value, strings);
+            }
+        }
+        _ => eprintln!("Ignoring non-string catalog entry {}", prefix),
+    }
+}
+
+/// Language code from the environment (`LC_ALL`, `LC_MESSAGES`, `LANG`), e.g. `uk` for `uk_UA.UTF-8`
+pub fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            let code = value.split(['_', '.', '@']).next()?.to_lowercase();
+            (!code.is_empty() && code != "c" && code != "posix").then_some(code)
+        })
+}
+
+/// LocaleLibrary - built-in catalogs plus any found under `<assets>/locales/`
+pub struct LocaleLibrary {
+    locales: Vec<Locale>,
+}
+
+impl LocaleLibrary {
+    pub fn load(assets: &AssetResolver) -> Self {
+        let builtins: Vec<Locale> = BUILTIN_LOCALES
+            .iter()
+            .filter_map(|(id, text)| Locale::parse(id, text).map_err(|e| eprintln!("{}", e)).ok())
+            .collect();
+        let locales = assets.merge_from_disk(LOCALES_DIR, "catalog", builtins, |l| &l.id, |path| {
+            Locale::load_file(path).map(|locale| vec![locale])
+        });
+
+        LocaleLibrary { locales }
+    }
+
+    pub fn locales(&self) -> &[Locale] {
+        &self.locales
+    }
+
+    /// Catalog for `id`, or the system language when `None`, with English filling any gaps
+    pub fn get(&self, id: Option<&str>) -> Locale {
+        let english = self.find(DEFAULT_LANGUAGE).cloned().unwrap_or_default();
+        let wanted = id.map(str::to_string).or_else(system_language);
+        match wanted.as_deref().and_then(|id| self.find(id)) {
+            Some(locale) => locale.clone().with_fallback(&english),
+            None => english,
+        }
+    }
+
+    fn find(&self, id: &str) -> Option<&Locale> {
+        self.locales.iter().find(|l| l.id == id)
+    }
+}
@@ -1,22 +1,53 @@
+mod accessibility;
+mod achievements;
+mod achievements_state;
+mod animation;
 mod app;
+mod asset_pack;
+mod assets;
 mod audio;
+mod capture;
+mod cli;
 mod config;
+mod credits_state;
+mod daily;
+mod daily_state;
+mod display;
 mod fonts;
 mod game_state;
+mod ghost;
 mod input;
 mod input_handler;
 mod leaderboard_state;
+mod level;
+mod locale;
 mod menu_state;
+mod occupancy;
 mod persistence;
 mod rendering;
+mod replay;
+mod replay_render;
 mod resources;
+mod settings_state;
+mod stats_state;
+mod terminal;
 
 use app::App;
+use assets::AssetResolver;
+use cli::CliArgs;
 use config::Config;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = CliArgs::parse()?;
     let config = Config::load()?;
-    let mut app = App::new(config)?;
+    let assets = AssetResolver::new(cli.assets_dir);
+    if let Some(render) = &cli.render {
+        return replay_render::render(config, assets, render);
+    }
+    if cli.terminal {
+        return terminal::run(config, assets);
+    }
+    let mut app = App::new(config, assets)?;
     app.run();
     Ok(())
 }
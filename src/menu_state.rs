@@ -1,10 +1,29 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuItem {
     Start,
+    Daily,
     Leaderboard,
+    Stats,
+    Achievements,
+    Settings,
     Exit,
 }
 
+impl MenuItem {
+    /// Catalog key of the item's label
+    pub fn label_key(self) -> &'static str {
+        match self {
+            MenuItem::Start => "menu.start",
+            MenuItem::Daily => "menu.daily",
+            MenuItem::Leaderboard => "menu.leaderboard",
+            MenuItem::Stats => "menu.stats",
+            MenuItem::Achievements => "menu.achievements",
+            MenuItem::Settings => "menu.settings",
+            MenuItem::Exit => "menu.exit",
+        }
+    }
+}
+
 pub struct MenuState {
     pub selected_item: MenuItem,
 }
@@ -19,20 +38,36 @@ impl MenuState {
     pub fn move_up(&mut self) {
         self.selected_item = match self.selected_item {
             MenuItem::Start => MenuItem::Exit,
-            MenuItem::Leaderboard => MenuItem::Start,
-            MenuItem::Exit => MenuItem::Leaderboard,
+            MenuItem::Daily => MenuItem::Start,
+            MenuItem::Leaderboard => MenuItem::Daily,
+            MenuItem::Stats => MenuItem::Leaderboard,
+            MenuItem::Achievements => MenuItem::Stats,
+            MenuItem::Settings => MenuItem::Achievements,
+            MenuItem::Exit => MenuItem::Settings,
         };
     }
 
     pub fn move_down(&mut self) {
         self.selected_item = match self.selected_item {
-            MenuItem::Start => MenuItem::Leaderboard,
-            MenuItem::Leaderboard => MenuItem::Exit,
+            MenuItem::Start => MenuItem::Daily,
+            MenuItem::Daily => MenuItem::Leaderboard,
+            MenuItem::Leaderboard => MenuItem::Stats,
+            MenuItem::Stats => MenuItem::Achievements,
+            MenuItem::Achievements => MenuItem::Settings,
+            MenuItem::Settings => MenuItem::Exit,
             MenuItem::Exit => MenuItem::Start,
         };
     }
 
     pub fn get_items() -> Vec<MenuItem> {
-        vec![MenuItem::Start, MenuItem::Leaderboard, MenuItem::Exit]
+        vec![
+            MenuItem::Start,
+            MenuItem::Daily,
+            MenuItem::Leaderboard,
+            MenuItem::Stats,
+            MenuItem::Achievements,
+            MenuItem::Settings,
+            MenuItem::Exit,
+        ]
     }
 }
@@ -0,0 +1,92 @@
+// Occupancy grid - what fills each cell, kept up to date as the snake moves
+//
+// Collision checks and food placement look cells up here instead of
+// scanning the snake's body, so both stay constant-time on huge grids.
+use crate::game_state::Position;
+
+/// What fills one grid cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Wall,
+    Snake,
+}
+
+/// OccupancyGrid - a cell-owner array plus the list of empty cells
+///
+/// The empty cells are kept in an unordered list with each cell's slot in it,
+/// so a cell can be taken or freed, and a random empty cell picked, in O(1).
+#[derive(Debug, Clone)]
+pub struct OccupancyGrid {
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+    /// Index of every empty cell, in no particular order
+    free: Vec<u32>,
+    /// Where each empty cell's index sits in `free`
+    free_slot: Vec<u32>,
+}
+
+impl OccupancyGrid {
+    /// An all-empty grid
+    pub fn new(width: u32, height: u32) -> Self {
+        let count = width * height;
+        OccupancyGrid {
+            width,
+            height,
+            cells: vec![Cell::Empty; count as usize],
+            free: (0..count).collect(),
+            free_slot: (0..count).collect(),
+        }
+    }
+
+    /// What fills `pos`; anything past the edge counts as wall
+    pub fn get(&self, pos: Position) -> Cell {
+        self.index(pos).map_or(Cell::Wall, |i| self.cells[i as usize])
+    }
+
+    /// Fill `pos` with `cell`; positions past the edge are ignored
+    pub fn set(&mut self, pos: Position, cell: Cell) {
+        let Some(index) = self.index(pos) else {
+            return;
+        };
+        let was_empty = self.cells[index as usize] == Cell::Empty;
+        self.cells[index as usize] = cell;
+
+        match (was_empty, cell == Cell::Empty) {
+            (true, false) => {
+                // Swap the last free cell into this one's slot
+                let slot = self.free_slot[index as usize];
+                let last = self.free.pop().expect("an empty cell is in the free list");
+                if last != index {
+                    self.free[slot as usize] = last;
+                    self.free_slot[last as usize] = slot;
+                }
+            }
+            (false, true) => {
+                self.free_slot[index as usize] = self.free.len() as u32;
+                self.free.push(index);
+            }
+            _ => {}
+        }
+    }
+
+    /// Number of empty cells
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    /// The `n`th empty cell, `n < free_count()`; the order is arbitrary but deterministic
+    pub fn free_cell(&self, n: usize) -> Position {
+        let index = self.free[n];
+        Position {
+            x: (index % self.width) as i32,
+            y: (index / self.width) as i32,
+        }
+    }
+
+    fn index(&self, pos: Position) -> Option<u32> {
+        let inside = pos.x >= 0 && pos.y >= 0 && (pos.x as u32) < self.width && (pos.y as u32) < self.height;
+        inside.then(|| pos.y as u32 * self.width + pos.x as u32)
+    }
+}
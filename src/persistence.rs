@@ -1,11 +1,32 @@
+use crate::accessibility::Accessibility;
+use crate::config::RulesConfig;
+use crate::display::DisplayMode;
+use crate::game_state::{DeathCause, GameState};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Mode name used for games without a level
+pub const OPEN_ARENA_MODE: &str = "open";
+
+/// Mode name used for daily challenges
+pub const DAILY_MODE: &str = "daily";
+
+/// Prefix of a level's mode name, so a level file called `open` or `daily` keeps its own best
+pub const LEVEL_MODE_PREFIX: &str = "level:";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ScoreEntry {
     pub name: String,
     pub score: u32,
+    /// The game ended with the board cleared
+    #[serde(default)]
+    pub cleared: bool,
+    /// Rules the game was played under; entries from before rule variants were classic
+    #[serde(default)]
+    pub rules: RulesConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,8 +57,8 @@ impl Leaderboard {
         Ok(())
     }
 
-    pub fn add_score(&mut self, name: String, score: u32) {
-        self.entries.push(ScoreEntry { name, score });
+    pub fn add_score(&mut self, name: String, score: u32, cleared: bool, rules: RulesConfig) {
+        self.entries.push(ScoreEntry { name, score, cleared, rules });
         self.entries.sort_by(|a, b| b.score.cmp(&a.score));
         self.entries.truncate(Self::MAX_ENTRIES);
     }
@@ -51,3 +72,266 @@ impl Leaderboard {
         PathBuf::from(Self::LEADERBOARD_FILE)
     }
 }
+
+/// The player's attempt at one day's challenge
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyResult {
+    /// UTC date as `YYYY-MM-DD`
+    pub date: String,
+    pub score: u32,
+}
+
+/// DailyHistory - one scored attempt per day, kept apart from the leaderboard
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DailyHistory {
+    /// Oldest first
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    const DAILY_FILE: &'static str = "daily.json";
+
+    pub fn load() -> Self {
+        let path = Self::get_path();
+        if let Ok(data) = fs::read_to_string(&path)
+            && let Ok(history) = serde_json::from_str(&data)
+        {
+            return history;
+        }
+        DailyHistory::default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::get_path();
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    pub fn result(&self, date: &str) -> Option<&DailyResult> {
+        self.results.iter().find(|r| r.date == date)
+    }
+
+    /// Use up the day's scored attempt; it counts as 0 until the game finishes
+    pub fn start_attempt(&mut self, date: &str) {
+        if self.result(date).is_none() {
+            self.results.push(DailyResult {
+                date: date.to_string(),
+                score: 0,
+            });
+        }
+    }
+
+    pub fn set_score(&mut self, date: &str, score: u32) {
+        if let Some(result) = self.results.iter_mut().find(|r| r.date == date) {
+            result.score = score;
+        }
+    }
+
+    fn get_path() -> PathBuf {
+        PathBuf::from(Self::DAILY_FILE)
+    }
+}
+
+/// One finished game in the profile's score history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameRecord {
+    pub score: u32,
+    pub mode: String,
+    pub duration_ms: u64,
+    /// Seconds since the Unix epoch when the game ended
+    pub finished_at: u64,
+}
+
+/// Profile - lifetime statistics across every session
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub games_played: u32,
+    pub total_food: u32,
+    /// Game time across all games; pauses don't count
+    pub total_time_ms: u64,
+    pub longest_snake: u32,
+    /// Best score for each mode (`LEVEL_MODE_PREFIX` and the level name, `OPEN_ARENA_MODE` or `DAILY_MODE`),
+    /// from games on the default rules only
+    pub best_by_mode: BTreeMap<String, u32>,
+    pub wall_deaths: u32,
+    pub self_deaths: u32,
+    pub boards_cleared: u32,
+    /// Most recent games, oldest first
+    pub history: Vec<GameRecord>,
+    /// Unlocked achievement ids, with when each was unlocked (seconds since the Unix epoch)
+    pub achievements: BTreeMap<String, u64>,
+}
+
+impl Profile {
+    const MAX_HISTORY: usize = 100;
+    const PROFILE_FILE: &'static str = "profile.json";
+
+    pub fn load() -> Self {
+        let path = Self::get_path();
+        if let Ok(data) = fs::read_to_string(&path)
+            && let Ok(profile) = serde_json::from_str(&data)
+        {
+            return profile;
+        }
+        Profile::default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::get_path();
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Add a finished game played in `mode` to the totals and history
+    pub fn record(&mut self, game: &GameState, mode: &str) {
+        self.games_played += 1;
+        self.total_food += game.foods_eaten;
+        self.total_time_ms += game.elapsed_ms;
+        self.longest_snake = self.longest_snake.max(game.snake.len() as u32);
+        // Custom rules make for a different game, so their scores don't count as a mode's best
+        if game.replay().gameplay.rules == RulesConfig::default() {
+            let best = self.best_by_mode.entry(mode.to_string()).or_default();
+            *best = (*best).max(game.score);
+        }
+        match game.death {
+            Some(DeathCause::Wall) => self.wall_deaths += 1,
+            Some(DeathCause::SelfCollision) => self.self_deaths += 1,
+            None => {}
+        }
+        if game.cleared {
+            self.boards_cleared += 1;
+        }
+
+        self.history.push(GameRecord {
+            score: game.score,
+            mode: mode.to_string(),
+            duration_ms: game.elapsed_ms,
+            finished_at: unix_time(),
+        });
+        let excess = self.history.len().saturating_sub(Self::MAX_HISTORY);
+        self.history.drain(..excess);
+    }
+
+    /// Mark an achievement unlocked now, unless it already was
+    pub fn unlock(&mut self, id: &str) {
+        self.achievements.entry(id.to_string()).or_insert_with(unix_time);
+    }
+
+    /// Mean game length in milliseconds
+    pub fn average_game_ms(&self) -> u64 {
+        self.total_time_ms / self.games_played.max(1) as u64
+    }
+
+    fn get_path() -> PathBuf {
+        PathBuf::from(Self::PROFILE_FILE)
+    }
+}
+
+/// Seconds since the Unix epoch
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Keep a finished game played in `mode`: its score on the leaderboard if high
+/// enough, its replay, and its place in the lifetime stats
+///
+/// Daily challenges go to the daily history instead of the leaderboard, and
+/// only the day's scored attempt is kept there.
+pub fn save_finished_game(game: &GameState, mode: &str) {
+    let mode = match game.daily() {
+        Some(run) => {
+            if run.scored {
+                let mut history = DailyHistory::load();
+                history.set_score(&run.challenge.date, game.score);
+                history.save().ok();
+                if let Err(e) = game.replay().save(&run.challenge.replay_path()) {
+                    eprintln!("Failed to save daily replay: {}", e);
+                }
+            }
+            DAILY_MODE
+        }
+        None => {
+            let mut leaderboard = Leaderboard::load();
+            if leaderboard.is_high_score(game.score) {
+                let rules = game.replay().gameplay.rules.clone();
+                leaderboard.add_score("PLAYER".to_string(), game.score, game.cleared, rules);
+                leaderboard.save().ok();
+            }
+            mode
+        }
+    };
+
+    let mut profile = Profile::load();
+    profile.record(game, mode);
+    if let Err(e) = profile.save() {
+        eprintln!("Failed to save profile: {}", e);
+    }
+
+    if let Err(e) = game.replay().save_finished() {
+        eprintln!("Failed to save replay: {}", e);
+    }
+    // The daily board comes back as practice; other seeds only come back through a retry
+    if game.daily().is_some() {
+        save_ghost(game);
+    }
+}
+
+/// Keep a finished game as the ghost for its seed, for a run that is about to race it
+pub fn save_ghost(game: &GameState) {
+    if let Err(e) = game.replay().save_ghost() {
+        eprintln!("Failed to save ghost: {}", e);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Settings {
+    /// Id of the active colour theme
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Language code of the UI catalog; `None` follows the system language
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Id of the asset pack layered over the defaults
+    #[serde(default)]
+    pub asset_pack: Option<String>,
+    /// Pack-relative path of the selected level
+    #[serde(default)]
+    pub level: Option<String>,
+    #[serde(default)]
+    pub accessibility: Accessibility,
+    #[serde(default)]
+    pub display_mode: DisplayMode,
+    /// Don't race ghosts of earlier runs on the same seed
+    #[serde(default)]
+    pub hide_ghost: bool,
+}
+
+impl Settings {
+    const SETTINGS_FILE: &'static str = "settings.json";
+
+    pub fn load() -> Self {
+        let path = Self::get_path();
+        if let Ok(data) = fs::read_to_string(&path)
+            && let Ok(settings) = serde_json::from_str(&data)
+        {
+            return settings;
+        }
+        Settings::default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::get_path();
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    fn get_path() -> PathBuf {
+        PathBuf::from(Self::SETTINGS_FILE)
+    }
+}
@@ -1,156 +1,543 @@
+use crate::accessibility::Accessibility;
+use crate::achievements::Achievement;
+use crate::achievements_state::AchievementsState;
+use crate::animation::{Clock, Easing, Tween};
 use crate::config::Config;
-use crate::game_state::{GameState, Position};
+use crate::daily_state::DailyState;
+use crate::fonts::{FontSize, FontSystem};
+use crate::game_state::{Direction, GameState, Position};
+use crate::ghost::Ghost;
 use crate::leaderboard_state::LeaderboardState;
-use crate::rendering::{colors, text_renderer::TextRenderer};
+use crate::locale::Locale;
+use crate::rendering::particles::{DEATH_SEGMENT_DELAY, ParticleSystem};
+use crate::rendering::post_fx::{GLOW_SCALE, PostEffects, PostEffectsConfig};
+use crate::rendering::sprites::{self, Tile, Tileset};
+use crate::rendering::row_spacing;
+use crate::rendering::target::DrawTarget;
+use crate::rendering::{text_renderer::TextRenderer, theme::Theme};
+use crate::stats_state::{self, StatsState};
+use std::collections::VecDeque;
 use sdl2::pixels::Color;
-use sdl2::rect::Rect;
-use sdl2::render::Canvas;
-use sdl2::ttf::Font;
-use sdl2::video::Window;
-use std::time::Instant;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas};
+
+/// Height of the HUD band above the playfield before the font size is known
+const DEFAULT_HUD_HEIGHT: u32 = 40;
+
+/// How long the playfield flash after eating lasts, in seconds
+const FLASH_SECONDS: f32 = 0.15;
+
+/// Peak opacity of the eat flash
+const FLASH_ALPHA: f32 = 60.0;
+
+/// How long the HUD score takes to roll up to a new score
+const SCORE_ROLL_SECONDS: f32 = 0.5;
+
+/// "GAME OVER" grows from this scale to full size
+const GAME_OVER_ZOOM_FROM: f32 = 0.3;
+const GAME_OVER_ZOOM_SECONDS: f32 = 0.5;
+
+/// How long an achievement toast stays up, including sliding in and out
+const TOAST_SECONDS: f32 = 3.5;
+const TOAST_SLIDE_SECONDS: f32 = 0.3;
+
+/// Opacity of the ghost snake
+const GHOST_ALPHA: u8 = 80;
+
+/// Space between the score and the queued-turn arrows, and between arrows
+const INPUT_ARROW_GAP: i32 = 8;
+
+/// Lists stop this far above the bottom edge, clear of the controls hint
+const LIST_BOTTOM_MARGIN: i32 = 90;
+
+/// The stats chart is left out when the window leaves less room than this
+const MIN_CHART_HEIGHT: i32 = 60;
+
+/// A popup announcing an unlocked achievement
+struct Toast {
+    name: String,
+    description: String,
+    /// When it first appeared; toasts wait their turn until then
+    shown_at: Option<f32>,
+}
 
 pub struct GameRenderer {
     text_renderer: TextRenderer,
     cell_size: u32,
-    grid_size: u32,
+    origin_x: i32,
+    origin_y: i32,
+    hud_height: u32,
+    grid_width: u32,
+    grid_height: u32,
+    grid_alpha: u8,
     window_width: u32,
     window_height: u32,
-    animation_start: Instant,
+    theme: Theme,
+    locale: Locale,
+    accessibility: Accessibility,
+    particles: ParticleSystem,
+    effects_enabled: bool,
+    /// Remaining eat flash, from 1.0 down to 0.0
+    flash: f32,
+    /// Seconds since the snake died, while its body is bursting apart
+    death_elapsed: Option<f32>,
+    /// Sprites from the theme's tilesheet, drawn instead of flat cells
+    tileset: Option<Tileset>,
+    post: PostEffects,
+    /// Score shown in the HUD, rolling up towards the real score
+    score_tween: Tween,
+    /// "GAME OVER" zooming in, from the moment the game ended
+    game_over_zoom: Option<Tween>,
+    /// Achievement popups, shown one at a time in order
+    toasts: VecDeque<Toast>,
+    /// Draw queued turns as arrows next to the score
+    show_input_queue: bool,
 }
 
 impl GameRenderer {
     pub fn new(config: &Config) -> Self {
-        let cell_size = config.visual.window_width / config.gameplay.grid_size;
         let text_renderer = TextRenderer::new();
-        GameRenderer {
+        let mut renderer = GameRenderer {
             text_renderer,
-            cell_size,
-            grid_size: config.gameplay.grid_size,
+            cell_size: 1,
+            origin_x: 0,
+            origin_y: 0,
+            hud_height: DEFAULT_HUD_HEIGHT,
+            grid_width: config.gameplay.grid_width(),
+            grid_height: config.gameplay.grid_height(),
+            grid_alpha: (config.visual.grid_alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
             window_width: config.visual.window_width,
             window_height: config.visual.window_height,
-            animation_start: Instant::now(),
+            theme: Theme::default(),
+            locale: Locale::default(),
+            accessibility: Accessibility::default(),
+            particles: ParticleSystem::new(config.visual.particle_density),
+            effects_enabled: config.visual.particles,
+            flash: 0.0,
+            death_elapsed: None,
+            tileset: None,
+            post: PostEffects::new(PostEffectsConfig::from_visual(&config.visual)),
+            score_tween: Tween::at(0.0),
+            game_over_zoom: None,
+            toasts: VecDeque::new(),
+            show_input_queue: config.input.show_queue,
+        };
+        renderer.resize(config.visual.window_width, config.visual.window_height, DEFAULT_HUD_HEIGHT);
+        renderer
+    }
+
+    /// Recompute the playfield for a new window size
+    ///
+    /// The HUD band sits across the top; the grid gets the largest whole-pixel cell
+    /// size that fits both dimensions below it and is centred, letterboxing any
+    /// leftover space.
+    pub fn resize(&mut self, window_width: u32, window_height: u32, hud_height: u32) {
+        self.window_width = window_width;
+        self.window_height = window_height;
+        self.hud_height = hud_height;
+
+        let available_height = window_height.saturating_sub(hud_height);
+        self.cell_size = (window_width / self.grid_width)
+            .min(available_height / self.grid_height)
+            .max(1);
+
+        let play_width = self.cell_size * self.grid_width;
+        let play_height = self.cell_size * self.grid_height;
+        self.origin_x = (window_width as i32 - play_width as i32) / 2;
+        self.origin_y = hud_height as i32 + (available_height as i32 - play_height as i32) / 2;
+    }
+
+    /// Switch to a different grid size, keeping the current window layout
+    pub fn set_grid_size(&mut self, (grid_width, grid_height): (u32, u32)) {
+        if (grid_width, grid_height) != (self.grid_width, self.grid_height) {
+            self.grid_width = grid_width;
+            self.grid_height = grid_height;
+            self.resize(self.window_width, self.window_height, self.hud_height);
         }
     }
 
-    pub fn render_game(&self, canvas: &mut Canvas<Window>, font: &Font, game: &GameState) {
-        canvas.set_draw_color(colors::BACKGROUND);
-        canvas.clear();
+    /// Pixel position of the top-left corner of a (possibly fractional) grid cell
+    fn cell_origin(&self, grid_x: f32, grid_y: f32) -> (i32, i32) {
+        (
+            self.origin_x + (grid_x * self.cell_size as f32).round() as i32,
+            self.origin_y + (grid_y * self.cell_size as f32).round() as i32,
+        )
+    }
 
-        // Draw grid lines
-        self.draw_grid(canvas);
+    pub fn set_accessibility(&mut self, accessibility: Accessibility) {
+        self.accessibility = accessibility;
+        self.post.set_reduced_motion(accessibility.reduced_motion);
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
 
-        // Draw food with pulse animation
-        self.draw_cell_pulsing(canvas, &game.food, colors::FOOD);
+    /// Switch to sprite drawing with a tilesheet, or back to flat cells
+    pub fn set_tileset(&mut self, tileset: Option<Tileset>) {
+        self.tileset = tileset;
+    }
+
+    /// Particles and flashes are skipped entirely when disabled or with reduced motion
+    fn effects_on(&self) -> bool {
+        self.effects_enabled && !self.accessibility.reduced_motion
+    }
 
-        // Draw snake with interpolation
-        if !game.snake.is_empty() {
-            // Draw body segments at grid positions (skip the head)
-            for segment in game.snake.iter().skip(1) {
-                self.draw_cell(canvas, segment, colors::SNAKE);
+    /// Advance effects by `dt` seconds; they freeze while the game is paused
+    pub fn update_effects(&mut self, game: &GameState, dt: f32, clock: &Clock) {
+        let now = clock.now();
+
+        // A restart cancels whatever was left of the last death
+        if !game.game_over && self.death_elapsed.is_some() {
+            self.death_elapsed = None;
+            self.particles.clear();
+        }
+
+        // Roll the HUD score up to a new score; a reset snaps straight back down
+        let target = game.score as f32;
+        if target != self.score_tween.to {
+            self.score_tween = if target > self.score_tween.to {
+                Tween::new(self.score_tween.value(now), target, now, SCORE_ROLL_SECONDS, Easing::CubicOut)
+            } else {
+                Tween::at(target)
+            };
+        }
+
+        match (game.finished(), self.game_over_zoom) {
+            (true, None) => {
+                let zoom = Tween::new(GAME_OVER_ZOOM_FROM, 1.0, now, GAME_OVER_ZOOM_SECONDS, Easing::BackOut);
+                self.game_over_zoom = Some(zoom);
             }
+            (false, Some(_)) => self.game_over_zoom = None,
+            _ => {}
+        }
+        if game.paused {
+            return;
+        }
 
-            // Draw head with interpolation for smooth movement
-            let (interp_x, interp_y) = game.get_interpolated_head();
-            self.draw_cell_interpolated(canvas, interp_x, interp_y, colors::SNAKE);
+        if let Some(toast) = self.toasts.front_mut() {
+            let shown_at = *toast.shown_at.get_or_insert(now);
+            if now - shown_at >= TOAST_SECONDS {
+                self.toasts.pop_front();
+            }
         }
 
-        // Draw score
-        self.text_renderer.draw_text(
-            canvas,
-            font,
-            &format!("Score: {}", game.score),
-            10,
-            10,
-            colors::TEXT,
-        ).ok();
+        self.particles.update(dt);
+        self.post.update(dt);
+        self.flash = (self.flash - dt / FLASH_SECONDS).max(0.0);
+        if let Some(elapsed) = &mut self.death_elapsed {
+            *elapsed += dt;
+        }
+    }
+
+    /// Sparks where the food was eaten, plus a ring on speed-up milestones
+    pub fn on_food_eaten(&mut self, game: &GameState) {
+        self.post.on_food_eaten();
+        if !self.effects_on() {
+            return;
+        }
+        let Some(head) = game.snake.front() else {
+            return;
+        };
+
+        self.particles.emit_food(head.x, head.y, self.theme.food.normal.0);
+        self.flash = 1.0;
+        if game.reached_speed_milestone() {
+            self.particles.emit_speed_milestone(head.x, head.y, self.theme.highlight.0);
+        }
+    }
+
+    /// Queue a popup announcing a newly unlocked achievement
+    pub fn show_achievement(&mut self, achievement: &Achievement) {
+        self.toasts.push_back(Toast {
+            name: achievement.name(&self.locale).to_string(),
+            description: achievement.description(&self.locale).to_string(),
+            shown_at: None,
+        });
+    }
+
+    /// Rings spreading from the head when the snake fills the board
+    pub fn on_board_cleared(&mut self, game: &GameState) {
+        if !self.effects_on() {
+            return;
+        }
+        let Some(head) = game.snake.front() else {
+            return;
+        };
+
+        self.particles.emit_speed_milestone(head.x, head.y, self.theme.highlight.0);
+        self.particles.emit_power_up(head.x, head.y, self.theme.food.normal.0);
+    }
+
+    /// Burst the snake apart segment by segment, head first
+    pub fn on_death(&mut self, game: &GameState) {
+        self.post.on_death();
+        if !self.effects_on() {
+            return;
+        }
+
+        let len = game.snake.len();
+        self.particles.emit_death(
+            game.snake
+                .iter()
+                .enumerate()
+                .map(|(i, segment)| (segment.x, segment.y, self.theme.snake_segment(i, len))),
+        );
+        self.death_elapsed = Some(0.0);
+    }
+
+    /// Whether segment `index` has already burst and should no longer be drawn
+    fn has_burst(&self, game: &GameState, index: usize) -> bool {
+        game.game_over
+            && self
+                .death_elapsed
+                .is_some_and(|elapsed| elapsed >= index as f32 * DEATH_SEGMENT_DELAY)
+    }
+
+    /// Draw the game, with `ghost` racing underneath the player's snake
+    pub fn render_game<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        game: &GameState,
+        ghost: Option<&Ghost>,
+        clock: &Clock,
+    ) {
+        let now = clock.now();
+        self.draw_scene(canvas, fonts, game, ghost, now);
 
         if game.paused {
             self.text_renderer.draw_text_centered(
                 canvas,
-                font,
-                "PAUSED",
+                fonts,
+                FontSize::Large,
+                self.locale.get("game.paused"),
                 (self.window_width / 2) as i32,
                 (self.window_height / 2) as i32,
-                colors::TEXT,
+                self.theme.text.0,
             ).ok();
             self.text_renderer.draw_text_centered(
                 canvas,
-                font,
-                "[ESC] Resume",
+                fonts,
+                FontSize::Small,
+                self.locale.get("game.resume_hint"),
                 (self.window_width / 2) as i32,
                 (self.window_height / 2 + 40) as i32,
-                colors::TEXT_DIM,
+                self.theme.text_dim.0,
             ).ok();
         }
 
         if game.game_over {
+            let final_score = self.locale.format("game.final_score", &[("score", game.score.to_string())]);
+            self.draw_end_overlay(canvas, fonts, "game.game_over", self.theme.text.0, &final_score, now);
+        }
+
+        self.post.draw_crt_overlay(canvas);
+    }
+
+    /// Draw the win screen: the filled board under the score and the time it took
+    pub fn render_cleared<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        game: &GameState,
+        clock: &Clock,
+    ) {
+        let now = clock.now();
+        self.draw_scene(canvas, fonts, game, None, now);
+
+        let final_score = self.locale.format("game.final_score", &[("score", game.score.to_string())]);
+        let time = stats_state::format_duration(game.elapsed_ms);
+        let details = format!("{}  {}", final_score, self.locale.format("game.cleared_time", &[("time", time)]));
+        self.draw_end_overlay(canvas, fonts, "game.cleared", self.theme.highlight.0, &details, now);
+
+        self.post.draw_crt_overlay(canvas);
+    }
+
+    /// Playfield, HUD and toasts, shared by the game and the win screen
+    fn draw_scene<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        game: &GameState,
+        ghost: Option<&Ghost>,
+        now: f32,
+    ) {
+        // The playfield may go through an offscreen texture for shake and chromatic flash
+        self.post.render_scene(canvas, self.theme.background.0, |canvas| {
+            canvas.set_draw_color(self.theme.background.0);
+            canvas.clear();
+
+            // Draw grid lines
+            self.draw_grid(canvas);
+
+            self.draw_glows(canvas, game, now);
+            if let Some(ghost) = ghost.filter(|g| !g.finished()) {
+                self.draw_ghost(canvas, ghost.game());
+            }
+            match &self.tileset {
+                Some(tileset) => self.draw_playfield_sprites(canvas, tileset, game, now),
+                None => self.draw_playfield(canvas, game, now),
+            }
+
+            self.draw_particles(canvas);
+            self.draw_flash(canvas);
+        });
+
+        // Draw score in the HUD band above the playfield
+        let hud_text_y = (self.hud_height as i32 - fonts.font().height()) / 2;
+        let score = self.locale.format(
+            "game.score",
+            &[("score", (self.score_tween.value(now).round() as u32).to_string())],
+        );
+        self.text_renderer.draw_text(
+            canvas,
+            fonts,
+            FontSize::Normal,
+            &score,
+            self.origin_x.max(10),
+            hud_text_y.max(0),
+            self.theme.text.0,
+        ).ok();
+
+        // Turns pressed ahead of the snake, waiting for their move
+        if self.show_input_queue
+            && let Ok(width) = self.text_renderer.text_width(canvas, fonts, FontSize::Normal, &score)
+        {
+            let x = self.origin_x.max(10) + width as i32 + INPUT_ARROW_GAP * 2;
+            let center_y = hud_text_y.max(0) + fonts.font().height() / 2;
+            self.draw_input_queue(canvas, game.queued_turns(), x, center_y, fonts.font().height());
+        }
+
+        // How far ahead of (or behind) the ghost the player is
+        if let Some(ghost) = ghost {
+            let delta = ghost.score_delta(game);
+            let text = self.locale.format("game.ghost", &[("delta", format!("{:+}", delta))]);
+            let color = if delta >= 0 { self.theme.highlight.0 } else { self.theme.text_dim.0 };
             self.text_renderer.draw_text_centered(
                 canvas,
-                font,
-                "GAME OVER",
-                (self.window_width / 2) as i32,
-                (self.window_height / 2 - 40) as i32,
-                colors::TEXT,
-            ).ok();
-            self.text_renderer.draw_text_centered(
-                canvas,
-                font,
-                &format!("Final Score: {}", game.score),
-                (self.window_width / 2) as i32,
-                (self.window_height / 2) as i32,
-                colors::TEXT,
-            ).ok();
-            self.text_renderer.draw_text_centered(
-                canvas,
-                font,
-                "[ESC] Menu | [Enter] Restart",
+                fonts,
+                FontSize::Normal,
+                &text,
                 (self.window_width / 2) as i32,
-                (self.window_height / 2 + 40) as i32,
-                colors::TEXT_DIM,
+                hud_text_y.max(0),
+                color,
             ).ok();
         }
 
-        canvas.present();
+        // Daily challenge tag on the right of the HUD
+        if let Some(run) = game.daily() {
+            let key = if run.scored { "daily.hud" } else { "daily.practice" };
+            let text = self.locale.format(key, &[("date", run.challenge.date.clone())]);
+            if let Ok(width) = self.text_renderer.text_width(canvas, fonts, FontSize::Normal, &text) {
+                let right = self.window_width as i32 - self.origin_x.max(10);
+                self.text_renderer.draw_text(
+                    canvas,
+                    fonts,
+                    FontSize::Normal,
+                    &text,
+                    right - width as i32,
+                    hud_text_y.max(0),
+                    self.theme.text_dim.0,
+                ).ok();
+            }
+        }
+
+        self.draw_toast(canvas, fonts, now);
     }
 
-    pub fn render_leaderboard(&self, canvas: &mut Canvas<Window>, font: &Font, state: &LeaderboardState) {
-        canvas.set_draw_color(colors::BACKGROUND);
+    /// A zooming title over the final score and the restart hint
+    fn draw_end_overlay<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        title_key: &str,
+        title_color: Color,
+        details: &str,
+        now: f32,
+    ) {
+        // Zoom the title in (held at full size for reduced motion)
+        let zoom = match self.game_over_zoom {
+            Some(tween) if !self.accessibility.reduced_motion => tween.value(now),
+            _ => 1.0,
+        };
+        let title_height = fonts.sized(FontSize::Large).height();
+        self.text_renderer.draw_text_scaled(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get(title_key),
+            (self.window_width / 2) as i32,
+            (self.window_height / 2) as i32 - 40 + title_height / 2,
+            zoom,
+            title_color,
+        ).ok();
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Normal,
+            details,
+            (self.window_width / 2) as i32,
+            (self.window_height / 2) as i32,
+            self.theme.text.0,
+        ).ok();
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("game.game_over_hint"),
+            (self.window_width / 2) as i32,
+            (self.window_height / 2 + 40) as i32,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    pub fn render_leaderboard<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, state: &LeaderboardState) {
+        canvas.set_draw_color(self.theme.background.0);
         canvas.clear();
 
         // Title
         self.text_renderer.draw_text_centered(
             canvas,
-            font,
-            "LEADERBOARD",
+            fonts,
+            FontSize::Large,
+            self.locale.get("leaderboard.title"),
             (self.window_width / 2) as i32,
             100,
-            colors::TEXT,
+            self.theme.text.0,
         ).ok();
 
         // Entries
         let start_y = 200;
-        let spacing = 50;
+        let bottom = self.window_height as i32 - LIST_BOTTOM_MARGIN;
+        let spacing = row_spacing(start_y, bottom, state.leaderboard.entries.len(), 50);
 
         if state.leaderboard.entries.is_empty() {
             self.text_renderer.draw_text_centered(
                 canvas,
-                font,
-                "No scores yet!",
+                fonts,
+                FontSize::Normal,
+                self.locale.get("leaderboard.empty"),
                 (self.window_width / 2) as i32,
-                (start_y + 100) as i32,
-                colors::TEXT_DIM,
+                start_y + 100,
+                self.theme.text_dim.0,
             ).ok();
         } else {
             for (i, entry) in state.leaderboard.entries.iter().enumerate() {
-                let y = start_y + (i as u32 * spacing);
-                let text = format!("{}. {} .......... {}", i + 1, entry.name, entry.score);
+                let y = start_y + i as i32 * spacing;
+                let text = LeaderboardState::entry_text(entry, i + 1, &self.locale);
                 self.text_renderer.draw_text_centered(
                     canvas,
-                    font,
+                    fonts,
+                    FontSize::Normal,
                     &text,
                     (self.window_width / 2) as i32,
-                    y as i32,
-                    colors::TEXT,
+                    y,
+                    if entry.cleared { self.theme.highlight.0 } else { self.theme.text.0 },
                 ).ok();
             }
         }
@@ -158,40 +545,616 @@ impl GameRenderer {
         // Controls hint
         self.text_renderer.draw_text_centered(
             canvas,
-            font,
-            "[ESC] Return",
+            fonts,
+            FontSize::Small,
+            self.locale.get("leaderboard.hint"),
             (self.window_width / 2) as i32,
-            (self.window_height - 50) as i32,
-            colors::TEXT_DIM,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    pub fn render_daily<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, state: &DailyState) {
+        canvas.set_draw_color(self.theme.background.0);
+        canvas.clear();
+        let center_x = (self.window_width / 2) as i32;
+
+        // Title and date
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get("daily.title"),
+            center_x,
+            100,
+            self.theme.text.0,
+        ).ok();
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            &state.challenge.date,
+            center_x,
+            160,
+            self.theme.text_dim.0,
+        ).ok();
+
+        // Today's rules, then whether the scored attempt is used
+        let line_height = fonts.sized(FontSize::Normal).height() + 12;
+        let mut y = 220;
+        for line in state.rules(&self.locale) {
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Normal,
+                &line,
+                center_x,
+                y,
+                self.theme.text.0,
+            ).ok();
+            y += line_height;
+        }
+        y += line_height / 2;
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            &state.status(&self.locale),
+            center_x,
+            y,
+            self.theme.highlight.0,
+        ).ok();
+
+        // Earlier days
+        let small_height = fonts.sized(FontSize::Small).height() + 10;
+        y += line_height * 2;
+        let mut previous = state.previous().peekable();
+        if previous.peek().is_some() {
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Small,
+                self.locale.get("daily.history"),
+                center_x,
+                y,
+                self.theme.text_dim.0,
+            ).ok();
+            y += small_height;
+        }
+        // Only as many earlier days as fit above the hint
+        let bottom = self.window_height as i32 - LIST_BOTTOM_MARGIN;
+        for result in previous {
+            if y > bottom {
+                break;
+            }
+            let text = self.locale.format(
+                "daily.entry",
+                &[("date", result.date.clone()), ("score", result.score.to_string())],
+            );
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Small,
+                &text,
+                center_x,
+                y,
+                self.theme.text.0,
+            ).ok();
+            y += small_height;
+        }
+
+        // Controls hint
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("daily.hint"),
+            center_x,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    pub fn render_stats<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, state: &StatsState) {
+        canvas.set_draw_color(self.theme.background.0);
+        canvas.clear();
+        let center_x = (self.window_width / 2) as i32;
+
+        // Title
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get("stats.title"),
+            center_x,
+            100,
+            self.theme.text.0,
+        ).ok();
+
+        if state.profile.games_played == 0 {
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Normal,
+                self.locale.get("stats.empty"),
+                center_x,
+                300,
+                self.theme.text_dim.0,
+            ).ok();
+        } else {
+            // Totals, one per line
+            let line_height = fonts.sized(FontSize::Small).height() + 8;
+            let mut y = 170;
+            let bottom = self.window_height as i32 - LIST_BOTTOM_MARGIN;
+            for line in state.summary(&self.locale) {
+                if y > bottom {
+                    break;
+                }
+                self.text_renderer.draw_text_centered(
+                    canvas,
+                    fonts,
+                    FontSize::Small,
+                    &line,
+                    center_x,
+                    y,
+                    self.theme.text.0,
+                ).ok();
+                y += line_height;
+            }
+
+            // Score history in whatever room is left above the hint
+            let points = state.chart();
+            let chart_top = y + line_height * 2;
+            let chart_bottom = self.window_height as i32 - 90;
+            if chart_bottom - chart_top >= MIN_CHART_HEIGHT {
+                let label = self.locale.format("stats.history", &[("count", points.len().to_string())]);
+                self.text_renderer.draw_text_centered(
+                    canvas,
+                    fonts,
+                    FontSize::Small,
+                    &label,
+                    center_x,
+                    y + line_height / 2,
+                    self.theme.text_dim.0,
+                ).ok();
+
+                let margin = (self.window_width / 10) as i32;
+                let area = Rect::new(
+                    margin,
+                    chart_top,
+                    (self.window_width as i32 - margin * 2).max(1) as u32,
+                    (chart_bottom - chart_top) as u32,
+                );
+                self.draw_score_chart(canvas, area, &points);
+            }
+        }
+
+        // Controls hint
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("stats.hint"),
+            center_x,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
         ).ok();
+    }
+
+    pub fn render_achievements<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        state: &AchievementsState,
+    ) {
+        canvas.set_draw_color(self.theme.background.0);
+        canvas.clear();
+        let center_x = (self.window_width / 2) as i32;
 
-        canvas.present();
+        // Title and progress
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get("achievements.title"),
+            center_x,
+            100,
+            self.theme.text.0,
+        ).ok();
+        let progress = self.locale.format(
+            "achievements.progress",
+            &[
+                ("unlocked", state.unlocked_count().to_string()),
+                ("total", state.definitions.len().to_string()),
+            ],
+        );
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            &progress,
+            center_x,
+            160,
+            self.theme.text_dim.0,
+        ).ok();
+
+        // As many entries as fit, scrolled to keep the selected one in view
+        let name_height = fonts.sized(FontSize::Normal).height();
+        let entry_height = name_height + fonts.sized(FontSize::Small).height() + 20;
+        let top = 210;
+        let visible = ((self.window_height as i32 - 100 - top) / entry_height).max(1) as usize;
+        let first = state.selected.saturating_sub(visible - 1);
+
+        for (i, achievement) in state.definitions.iter().enumerate().skip(first).take(visible) {
+            let y = top + (i - first) as i32 * entry_height;
+            let unlocked = state.is_unlocked(achievement);
+            let name = achievement.name(&self.locale);
+            let name = if i == state.selected { format!("> {} <", name) } else { name.to_string() };
+            let color = if unlocked { self.theme.highlight.0 } else { self.theme.text_dim.0 };
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Normal,
+                &name,
+                center_x,
+                y,
+                color,
+            ).ok();
+
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Small,
+                &state.description(achievement, &self.locale),
+                center_x,
+                y + name_height + 4,
+                self.theme.text_dim.0,
+            ).ok();
+        }
+
+        // Controls hint
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("achievements.hint"),
+            center_x,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    /// The current achievement toast, sliding down from the top edge and back
+    fn draw_toast<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, now: f32) {
+        let Some(toast) = self.toasts.front() else {
+            return;
+        };
+        let Some(shown_at) = toast.shown_at else {
+            return;
+        };
+
+        let header = self.locale.get("achievements.unlocked");
+        let header_height = fonts.sized(FontSize::Small).height();
+        let name_height = fonts.sized(FontSize::Normal).height();
+        let height = (header_height * 2 + name_height + 32) as u32;
+        let width = self.window_width.saturating_sub(40).min(560);
+
+        // Slide in, hold, slide out; reduced motion just shows it
+        let age = now - shown_at;
+        let shown = if self.accessibility.reduced_motion {
+            1.0
+        } else {
+            let t = (age / TOAST_SLIDE_SECONDS).min((TOAST_SECONDS - age) / TOAST_SLIDE_SECONDS);
+            Easing::CubicOut.apply(t.clamp(0.0, 1.0))
+        };
+        let rest_y = self.hud_height as i32 + 8;
+        let y = (rest_y as f32 - (1.0 - shown) * (rest_y + height as i32) as f32).round() as i32;
+        let x = (self.window_width as i32 - width as i32) / 2;
+        let panel = Rect::new(x, y, width, height);
+
+        canvas.set_blend_mode(BlendMode::Blend);
+        let background = self.theme.background.0;
+        canvas.set_draw_color(Color::RGBA(background.r, background.g, background.b, 230));
+        canvas.fill_rect(panel).ok();
+        canvas.set_draw_color(self.theme.highlight.0);
+        canvas.draw_rect(panel).ok();
+
+        let center_x = (self.window_width / 2) as i32;
+        let mut text_y = y + 10;
+        for (text, size, color, line_height) in [
+            (header, FontSize::Small, self.theme.highlight.0, header_height),
+            (toast.name.as_str(), FontSize::Normal, self.theme.text.0, name_height),
+            (toast.description.as_str(), FontSize::Small, self.theme.text_dim.0, header_height),
+        ] {
+            self.text_renderer.draw_text_centered(canvas, fonts, size, text, center_x, text_y, color).ok();
+            text_y += line_height + 6;
+        }
+    }
+
+    /// Bars for each game's score, with a line tracing the best score so far
+    fn draw_score_chart<T: DrawTarget>(&self, canvas: &mut Canvas<T>, area: Rect, points: &[(u32, u32)]) {
+        let max = points.iter().map(|&(_, best)| best).max().unwrap_or(0).max(10) as f32;
+        let height_of = |score: u32| (score as f32 / max * area.height() as f32).round() as i32;
+        let slot = area.width() as f32 / points.len().max(1) as f32;
+        let gap = (slot / 5.0).max(1.0);
+
+        canvas.set_draw_color(self.theme.snake_tail.0);
+        for (i, &(score, _)) in points.iter().enumerate() {
+            let bar_height = height_of(score);
+            if bar_height > 0 {
+                let x = area.x() + (i as f32 * slot + gap / 2.0).round() as i32;
+                let bar = Rect::new(x, area.bottom() - bar_height, (slot - gap).max(1.0) as u32, bar_height as u32);
+                canvas.fill_rect(bar).ok();
+            }
+        }
+
+        let best_line: Vec<Point> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, best))| {
+                let x = area.x() + ((i as f32 + 0.5) * slot).round() as i32;
+                Point::new(x, area.bottom() - height_of(best))
+            })
+            .collect();
+        canvas.set_draw_color(self.theme.highlight.0);
+        canvas.draw_lines(best_line.as_slice()).ok();
+
+        // Axes
+        canvas.set_draw_color(self.theme.text_dim.0);
+        canvas.draw_line(area.bottom_left(), Point::new(area.right(), area.bottom())).ok();
+        canvas.draw_line(area.top_left(), area.bottom_left()).ok();
     }
 
-    fn draw_grid(&self, canvas: &mut Canvas<Window>) {
-        canvas.set_draw_color(colors::GRID);
+    fn draw_grid<T: DrawTarget>(&self, canvas: &mut Canvas<T>) {
+        let grid = self.theme.grid.0;
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(grid.r, grid.g, grid.b, self.grid_alpha));
+
+        let play_width = (self.grid_width * self.cell_size) as i32;
+        let play_height = (self.grid_height * self.cell_size) as i32;
+        let (left, top) = (self.origin_x, self.origin_y);
 
         // Vertical lines
-        for i in 0..=self.grid_size {
-            let x = (i * self.cell_size) as i32;
+        for i in 0..=self.grid_width {
+            let x = left + (i * self.cell_size) as i32;
             canvas
-                .draw_line((x, 0), (x, self.window_height as i32))
+                .draw_line((x, top), (x, top + play_height))
                 .ok();
         }
 
         // Horizontal lines
-        for i in 0..=self.grid_size {
-            let y = (i * self.cell_size) as i32;
+        for i in 0..=self.grid_height {
+            let y = top + (i * self.cell_size) as i32;
             canvas
-                .draw_line((0, y), (self.window_width as i32, y))
+                .draw_line((left, y), (left + play_width, y))
                 .ok();
         }
+
+        // Solid border so the arena edge stays visible against the letterbox
+        canvas.set_draw_color(grid);
+        canvas
+            .draw_rect(Rect::new(left - 1, top - 1, play_width as u32 + 2, play_height as u32 + 2))
+            .ok();
     }
 
-    fn draw_cell(&self, canvas: &mut Canvas<Window>, pos: &Position, color: Color) {
+    /// Walls, food and snake drawn as flat cells
+    fn draw_playfield<T: DrawTarget>(&self, canvas: &mut Canvas<T>, game: &GameState, now: f32) {
+        // Draw level walls, crossed out when shape cues are on
+        for wall in &game.walls {
+            self.draw_cell(canvas, wall, self.theme.wall.0);
+            if self.accessibility.shape_cues {
+                self.draw_cell_cross(canvas, wall, self.theme.background.0);
+            }
+        }
+
+        // Draw food with pulse animation (a diamond when shape cues are on); a cleared board has none
+        if !game.cleared {
+            self.draw_cell_pulsing(canvas, &game.food, self.theme.food.normal.0, now);
+        }
+
+        // Draw snake with every segment interpolated, shading head to tail
+        let len = game.snake.len();
+        let body: Vec<(f32, f32)> = game.interpolated_body().collect();
+
+        // Fill the cells where the body turns, so corners stay solid while segments slide around them
+        for i in 1..len.saturating_sub(1) {
+            let (prev, cell, next) = (game.snake[i - 1], game.snake[i], game.snake[i + 1]);
+            let turns = prev.x - cell.x != cell.x - next.x || prev.y - cell.y != cell.y - next.y;
+            if turns && !self.has_burst(game, i) {
+                self.draw_segment(canvas, cell.x as f32, cell.y as f32, self.theme.snake_segment(i, len));
+            }
+        }
+
+        // Tail first so the head is drawn on top
+        for (i, &(x, y)) in body.iter().enumerate().rev() {
+            if self.has_burst(game, i) {
+                continue;
+            }
+            if i == 0 {
+                self.draw_cell_interpolated(canvas, x, y, self.theme.snake_head.0);
+            } else {
+                self.draw_segment(canvas, x, y, self.theme.snake_segment(i, len));
+            }
+        }
+    }
+
+    /// Walls, food and snake drawn from the theme's tilesheet
+    ///
+    /// Body tiles stay on their cells so joints line up; the head and tail
+    /// slide, with a piece under the tail bridging the cell it is leaving.
+    fn draw_playfield_sprites<T: DrawTarget>(&self, canvas: &mut Canvas<T>, tileset: &Tileset, game: &GameState, now: f32) {
+        for wall in &game.walls {
+            self.draw_tile(canvas, tileset, Tile::Wall, wall.x as f32, wall.y as f32, 1.0);
+        }
+
+        if !game.cleared {
+            let food_tile = tileset.food_tile(game.food);
+            self.draw_tile(canvas, tileset, food_tile, game.food.x as f32, game.food.y as f32, self.pulse_scale(now));
+        }
+
+        let snake = &game.snake;
+        let len = snake.len();
+        let body: Vec<(f32, f32)> = game.interpolated_body().collect();
+
+        for i in (1..len.saturating_sub(1)).rev() {
+            if !self.has_burst(game, i) {
+                let tile = sprites::body_tile(snake[i - 1], snake[i], snake[i + 1]);
+                self.draw_tile(canvas, tileset, tile, snake[i].x as f32, snake[i].y as f32, 1.0);
+            }
+        }
+
+        if len >= 2 && !self.has_burst(game, len - 1) {
+            let tail = snake[len - 1];
+            if let Some(previous) = game.previous_tail().filter(|p| *p != tail) {
+                let tile = sprites::body_tile(snake[len - 2], tail, previous);
+                self.draw_tile(canvas, tileset, tile, tail.x as f32, tail.y as f32, 1.0);
+            }
+            let direction = sprites::side(tail, snake[len - 2]).unwrap_or(game.direction);
+            let (x, y) = body[len - 1];
+            self.draw_tile(canvas, tileset, Tile::Tail(direction), x, y, 1.0);
+        }
+
+        if let Some(&head) = snake.front()
+            && !self.has_burst(game, 0)
+        {
+            let direction = snake.get(1).and_then(|neck| sprites::side(*neck, head)).unwrap_or(game.direction);
+            let (x, y) = body[0];
+            self.draw_tile(canvas, tileset, Tile::Head(direction), x, y, 1.0);
+        }
+    }
+
+    /// Copy one tile over a (possibly fractional) grid cell, scaled about its centre
+    fn draw_tile<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        tileset: &Tileset,
+        tile: Tile,
+        grid_x: f32,
+        grid_y: f32,
+        scale: f32,
+    ) {
+        let (x, y) = self.cell_origin(grid_x, grid_y);
+        let size = (self.cell_size as f32 * scale).round().max(1.0) as u32;
+        let offset = (self.cell_size as i32 - size as i32) / 2;
+        canvas
+            .copy(tileset.texture(), tileset.source_rect(tile), Rect::new(x + offset, y + offset, size, size))
+            .ok();
+    }
+
+    /// `turns` as filled arrows `size` pixels across, left to right from `x`
+    fn draw_input_queue<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        turns: impl Iterator<Item = Direction>,
+        x: i32,
+        center_y: i32,
+        size: i32,
+    ) {
+        let half = size / 2;
+        canvas.set_draw_color(self.theme.highlight.0);
+        for (i, direction) in turns.enumerate() {
+            let center_x = x + half + i as i32 * (size + INPUT_ARROW_GAP);
+            // One line per row of the triangle, widening from the tip to the base
+            for row in 0..=half * 2 {
+                let spread = row / 2;
+                let (from, to) = match direction {
+                    Direction::Up => {
+                        let y = center_y - half + row;
+                        ((center_x - spread, y), (center_x + spread, y))
+                    }
+                    Direction::Down => {
+                        let y = center_y + half - row;
+                        ((center_x - spread, y), (center_x + spread, y))
+                    }
+                    Direction::Left => {
+                        let x = center_x - half + row;
+                        ((x, center_y - spread), (x, center_y + spread))
+                    }
+                    Direction::Right => {
+                        let x = center_x + half - row;
+                        ((x, center_y - spread), (x, center_y + spread))
+                    }
+                };
+                canvas.draw_line(from, to).ok();
+            }
+        }
+    }
+
+    /// The ghost's snake, translucent and outlined when shape cues are on
+    fn draw_ghost<T: DrawTarget>(&self, canvas: &mut Canvas<T>, ghost: &GameState) {
+        canvas.set_blend_mode(BlendMode::Blend);
+        let len = ghost.snake.len();
+        for (i, (x, y)) in ghost.interpolated_body().enumerate() {
+            let color = self.theme.snake_segment(i, len);
+            let color = Color::RGBA(color.r, color.g, color.b, GHOST_ALPHA);
+            if self.accessibility.shape_cues {
+                self.draw_cell_outline(canvas, x, y, color);
+            } else {
+                self.draw_cell_interpolated(canvas, x, y, color);
+            }
+        }
+    }
+
+    /// Additive neon halos under the food and every visible snake segment
+    fn draw_glows<T: DrawTarget>(&self, canvas: &mut Canvas<T>, game: &GameState, now: f32) {
+        let cell = self.cell_size as f32;
+        let half = self.cell_size as i32 / 2;
+        let diameter = (cell * GLOW_SCALE) as u32;
+
+        if !game.cleared {
+            let food = self.theme.food.normal.0;
+            let (x, y) = self.cell_origin(game.food.x as f32, game.food.y as f32);
+            let food_diameter = (diameter as f32 * self.pulse_scale(now)) as u32;
+            self.post
+                .draw_glow(canvas, (x + half, y + half), food_diameter, Color::RGBA(food.r, food.g, food.b, 200));
+        }
+
+        let len = game.snake.len();
+        for (i, (gx, gy)) in game.interpolated_body().enumerate() {
+            if self.has_burst(game, i) {
+                continue;
+            }
+            let color = self.theme.snake_segment(i, len);
+            let alpha = if i == 0 { 170 } else { 70 };
+            let (x, y) = self.cell_origin(gx, gy);
+            self.post
+                .draw_glow(canvas, (x + half, y + half), diameter, Color::RGBA(color.r, color.g, color.b, alpha));
+        }
+    }
+
+    fn draw_particles<T: DrawTarget>(&self, canvas: &mut Canvas<T>) {
+        canvas.set_blend_mode(BlendMode::Blend);
+        for particle in self.particles.particles().iter().filter(|p| p.is_visible()) {
+            let size = ((particle.size * self.cell_size as f32) as u32).max(1);
+            let (x, y) = self.cell_origin(particle.x, particle.y);
+            canvas.set_draw_color(particle.color());
+            canvas
+                .fill_rect(Rect::new(x - size as i32 / 2, y - size as i32 / 2, size, size))
+                .ok();
+        }
+    }
+
+    /// Brief wash of the highlight colour over the playfield after eating
+    fn draw_flash<T: DrawTarget>(&self, canvas: &mut Canvas<T>) {
+        if self.flash <= 0.0 {
+            return;
+        }
+        let color = self.theme.highlight.0;
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(color.r, color.g, color.b, (self.flash * FLASH_ALPHA) as u8));
+        canvas
+            .fill_rect(Rect::new(
+                self.origin_x,
+                self.origin_y,
+                self.grid_width * self.cell_size,
+                self.grid_height * self.cell_size,
+            ))
+            .ok();
+    }
+
+    fn draw_cell<T: DrawTarget>(&self, canvas: &mut Canvas<T>, pos: &Position, color: Color) {
         canvas.set_draw_color(color);
-        let x = (pos.x as u32 * self.cell_size) as i32;
-        let y = (pos.y as u32 * self.cell_size) as i32;
+        let (x, y) = self.cell_origin(pos.x as f32, pos.y as f32);
         let size = self.cell_size as u32;
 
         // Draw with small padding for segmented look
@@ -200,16 +1163,55 @@ impl GameRenderer {
             .fill_rect(Rect::new(
                 x + padding,
                 y + padding,
-                size - (padding * 2) as u32,
-                size - (padding * 2) as u32,
+                size.saturating_sub((padding * 2) as u32),
+                size.saturating_sub((padding * 2) as u32),
             ))
             .ok();
     }
 
-    fn draw_cell_interpolated(&self, canvas: &mut Canvas<Window>, grid_x: f32, grid_y: f32, color: Color) {
+    /// Body segment at a (possibly fractional) grid position, hollow when shape cues are on
+    fn draw_segment<T: DrawTarget>(&self, canvas: &mut Canvas<T>, grid_x: f32, grid_y: f32, color: Color) {
+        if self.accessibility.shape_cues {
+            self.draw_cell_outline(canvas, grid_x, grid_y, color);
+        } else {
+            self.draw_cell_interpolated(canvas, grid_x, grid_y, color);
+        }
+    }
+
+    /// Hollow square, so body segments read differently from solid shapes
+    fn draw_cell_outline<T: DrawTarget>(&self, canvas: &mut Canvas<T>, grid_x: f32, grid_y: f32, color: Color) {
+        canvas.set_draw_color(color);
+        let (x, y) = self.cell_origin(grid_x, grid_y);
+        let size = self.cell_size;
+
+        let padding = 2;
+        let border = 3.min(size / 4).max(1);
+        for inset in 0..border {
+            let inner = size.saturating_sub((padding * 2) as u32 + inset * 2);
+            canvas
+                .draw_rect(Rect::new(
+                    x + padding + inset as i32,
+                    y + padding + inset as i32,
+                    inner,
+                    inner,
+                ))
+                .ok();
+        }
+    }
+
+    /// Diagonal cross over a cell
+    fn draw_cell_cross<T: DrawTarget>(&self, canvas: &mut Canvas<T>, pos: &Position, color: Color) {
+        canvas.set_draw_color(color);
+        let (x, y) = self.cell_origin(pos.x as f32, pos.y as f32);
+        let end = self.cell_size as i32 - 1;
+
+        canvas.draw_line((x, y), (x + end, y + end)).ok();
+        canvas.draw_line((x + end, y), (x, y + end)).ok();
+    }
+
+    fn draw_cell_interpolated<T: DrawTarget>(&self, canvas: &mut Canvas<T>, grid_x: f32, grid_y: f32, color: Color) {
         canvas.set_draw_color(color);
-        let x = (grid_x * self.cell_size as f32) as i32;
-        let y = (grid_y * self.cell_size as f32) as i32;
+        let (x, y) = self.cell_origin(grid_x, grid_y);
         let size = self.cell_size as u32;
 
         // Draw with small padding for segmented look
@@ -218,37 +1220,59 @@ impl GameRenderer {
             .fill_rect(Rect::new(
                 x + padding,
                 y + padding,
-                size - (padding * 2) as u32,
-                size - (padding * 2) as u32,
+                size.saturating_sub((padding * 2) as u32),
+                size.saturating_sub((padding * 2) as u32),
             ))
             .ok();
     }
 
-    fn draw_cell_pulsing(&self, canvas: &mut Canvas<Window>, pos: &Position, color: Color) {
+    /// Food pulse scale: a sine wave with a 1 second period, held still for reduced motion
+    fn pulse_scale(&self, now: f32) -> f32 {
+        if self.accessibility.reduced_motion {
+            return 1.0;
+        }
+        let pulse_factor = (now * std::f32::consts::TAU).sin(); // TAU = 2π
+        1.0 + (pulse_factor * 0.1) // Range: 0.9 to 1.1
+    }
+
+    fn draw_cell_pulsing<T: DrawTarget>(&self, canvas: &mut Canvas<T>, pos: &Position, color: Color, now: f32) {
         canvas.set_draw_color(color);
 
-        // Calculate pulse scale using sine wave (1 second period)
-        let elapsed = self.animation_start.elapsed().as_secs_f32();
-        let pulse_factor = (elapsed * std::f32::consts::TAU).sin(); // TAU = 2π
-        let scale = 1.0 + (pulse_factor * 0.1); // Range: 0.9 to 1.1
+        let scale = self.pulse_scale(now);
 
         // Calculate scaled size and centered position
         let base_size = self.cell_size as f32;
         let scaled_size = (base_size * scale) as u32;
         let size_diff = (base_size - scaled_size as f32) / 2.0;
 
-        let x = (pos.x as f32 * base_size + size_diff) as i32;
-        let y = (pos.y as f32 * base_size + size_diff) as i32;
+        let (cell_x, cell_y) = self.cell_origin(pos.x as f32, pos.y as f32);
+        let x = cell_x + size_diff as i32;
+        let y = cell_y + size_diff as i32;
 
         // Draw with small padding for segmented look
         let padding = 2;
-        canvas
-            .fill_rect(Rect::new(
-                x + padding,
-                y + padding,
-                scaled_size.saturating_sub((padding * 2) as u32),
-                scaled_size.saturating_sub((padding * 2) as u32),
-            ))
-            .ok();
+        let rect = Rect::new(
+            x + padding,
+            y + padding,
+            scaled_size.saturating_sub((padding * 2) as u32),
+            scaled_size.saturating_sub((padding * 2) as u32),
+        );
+        if self.accessibility.shape_cues {
+            Self::fill_diamond(canvas, rect);
+        } else {
+            canvas.fill_rect(rect).ok();
+        }
+    }
+
+    /// Fill a diamond inscribed in `rect` one scanline at a time
+    fn fill_diamond<T: DrawTarget>(canvas: &mut Canvas<T>, rect: Rect) {
+        let half_h = rect.height() as i32 / 2;
+        let center_x = rect.x() + rect.width() as i32 / 2;
+        for row in 0..rect.height() as i32 {
+            let from_middle = (row - half_h).abs();
+            let half_w = (rect.width() as i32 / 2) * (half_h - from_middle) / half_h.max(1);
+            let y = rect.y() + row;
+            canvas.draw_line((center_x - half_w, y), (center_x + half_w, y)).ok();
+        }
     }
 }
@@ -0,0 +1,182 @@
+// Golden-image tests - each screen drawn offscreen and compared with a reference PNG
+//
+// Screens are drawn to a `Canvas<Surface>` with SDL's software renderer and
+// the dummy video driver, so no display is needed. Reference images live in
+// `tests/golden/`; run with `UPDATE_GOLDEN=1` to write them after an
+// intended visual change, and check the new images in.
+use crate::accessibility::Accessibility;
+use crate::animation::Clock;
+use crate::assets::AssetResolver;
+use crate::config::{Config, DEFAULT_CONFIG};
+use crate::fonts::FontSystem;
+use crate::game_state::{DeathCause, Direction, GameState};
+use crate::locale::LocaleLibrary;
+use crate::menu_state::MenuState;
+use crate::rendering::{GameRenderer, MenuRenderer, ThemeLibrary};
+use sdl2::image::{LoadSurface, SaveSurface};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::Canvas;
+use sdl2::surface::Surface;
+use std::path::PathBuf;
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 600;
+/// Largest difference allowed in any colour channel of a pixel
+const CHANNEL_TOLERANCE: u8 = 8;
+/// Share of pixels allowed past the channel tolerance, for font rasterizer differences
+const MAX_DIFFERENT_PIXELS: f64 = 0.002;
+/// Food placement for the game screens
+const SEED: u64 = 42;
+
+/// Everything a screen needs, set up the same way every run
+struct Scene {
+    // The renderers' textures go before the canvas that made them
+    menu_renderer: MenuRenderer,
+    game_renderer: GameRenderer,
+    canvas: Canvas<Surface<'static>>,
+    fonts: FontSystem,
+    game: GameState,
+    clock: Clock,
+}
+
+impl Scene {
+    fn new() -> Self {
+        let config: Config = toml::from_str(DEFAULT_CONFIG).expect("built-in config parses");
+        let assets = AssetResolver::new(None);
+        let theme = ThemeLibrary::load(&assets).get(None);
+        let locale = LocaleLibrary::load(&assets).get(Some("en"));
+        let fonts = FontSystem::new(&assets, 16).expect("fonts load");
+        let canvas = Surface::new(WIDTH, HEIGHT, PixelFormatEnum::RGBA32)
+            .and_then(|surface| surface.into_canvas())
+            .expect("software canvas");
+
+        let mut game = GameState::new(config.clone());
+        game.reset_with_seed(SEED);
+
+        // Reduced motion holds pulses and zooms still, and skips the random particles
+        let accessibility = Accessibility {
+            reduced_motion: true,
+            ..Accessibility::default()
+        };
+        let mut menu_renderer = MenuRenderer::new(WIDTH, HEIGHT);
+        menu_renderer.set_theme(theme.clone());
+        menu_renderer.set_locale(locale.clone());
+        menu_renderer.set_reduced_motion(true);
+        let mut game_renderer = GameRenderer::new(&config);
+        game_renderer.set_theme(theme);
+        game_renderer.set_locale(locale);
+        game_renderer.set_accessibility(accessibility);
+        game_renderer.set_grid_size(game.grid_dimensions());
+        game_renderer.resize(WIDTH, HEIGHT, fonts.font().height().max(0) as u32 + 16);
+
+        Scene {
+            menu_renderer,
+            game_renderer,
+            canvas,
+            fonts,
+            game,
+            clock: Clock::new(),
+        }
+    }
+
+    /// A few moves into a game, with a turn so the snake has a corner
+    fn play_a_little(&mut self) {
+        for _ in 0..3 {
+            self.game.update();
+        }
+        self.game.set_direction(Direction::Down);
+        for _ in 0..2 {
+            self.game.update();
+        }
+    }
+
+    fn render_game(&mut self) {
+        self.game_renderer
+            .render_game(&mut self.canvas, &self.fonts, &self.game, None, &self.clock);
+    }
+
+    /// Compare the canvas with `tests/golden/{name}.png`, or write it when blessing
+    fn check(&self, name: &str) -> Result<(), String> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{}.png", name));
+        let actual = self.canvas.surface().convert_format(PixelFormatEnum::RGBA32)?;
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().expect("golden dir has a parent")).map_err(|e| e.to_string())?;
+            return actual.save(&path);
+        }
+
+        let expected = Surface::from_file(&path)
+            .map_err(|e| format!("{}: {} (run with UPDATE_GOLDEN=1 to create it)", path.display(), e))?
+            .convert_format(PixelFormatEnum::RGBA32)?;
+        if expected.size() != actual.size() {
+            return Err(format!("{}: expected {:?}, drew {:?}", name, expected.size(), actual.size()));
+        }
+
+        let different = count_different_pixels(&expected, &actual);
+        let allowed = (WIDTH * HEIGHT) as f64 * MAX_DIFFERENT_PIXELS;
+        if different as f64 > allowed {
+            let diff_path = path.with_extension("actual.png");
+            actual.save(&diff_path).ok();
+            return Err(format!(
+                "{}: {} pixels differ, at most {} allowed; drawn image saved to {}",
+                name,
+                different,
+                allowed as u32,
+                diff_path.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Pixels whose channels differ by more than the tolerance, in two RGBA32 surfaces of one size
+fn count_different_pixels(expected: &Surface, actual: &Surface) -> usize {
+    let width = expected.width() as usize * 4;
+    let rows = |surface: &Surface| {
+        let pitch = surface.pitch() as usize;
+        surface.with_lock(|pixels| {
+            pixels
+                .chunks(pitch)
+                .flat_map(|row| row[..width].iter().copied())
+                .collect::<Vec<u8>>()
+        })
+    };
+    let (expected, actual) = (rows(expected), rows(actual));
+    expected
+        .chunks(4)
+        .zip(actual.chunks(4))
+        .filter(|(a, b)| a.iter().zip(b.iter()).any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE))
+        .count()
+}
+
+/// All screens in one test: SDL may only be initialised once at a time
+#[test]
+fn screens_match_golden_images() {
+    sdl2::hint::set("SDL_VIDEODRIVER", "dummy");
+    let sdl = sdl2::init().expect("SDL init");
+    let _video = sdl.video().expect("dummy video driver");
+
+    let mut scene = Scene::new();
+    let mut failures = Vec::new();
+
+    scene.menu_renderer.render(&mut scene.canvas, &scene.fonts, &MenuState::new(), &scene.clock);
+    failures.extend(scene.check("menu").err());
+
+    scene.play_a_little();
+    scene.render_game();
+    failures.extend(scene.check("game").err());
+
+    scene.game.toggle_pause();
+    scene.render_game();
+    failures.extend(scene.check("pause").err());
+    scene.game.toggle_pause();
+
+    scene.game.game_over = true;
+    scene.game.death = Some(DeathCause::Wall);
+    scene.render_game();
+    failures.extend(scene.check("game_over").err());
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
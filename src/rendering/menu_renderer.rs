@@ -1,13 +1,34 @@
-use crate::menu_state::{MenuItem, MenuState};
-use crate::rendering::{colors, text_renderer::TextRenderer};
-use sdl2::render::Canvas;
-use sdl2::ttf::Font;
-use sdl2::video::Window;
+use crate::animation::{Clock, Easing, Transition, TransitionKind, Tween};
+use crate::credits_state::CreditsState;
+use crate::fonts::{FontSize, FontSystem};
+use crate::locale::Locale;
+use crate::menu_state::MenuState;
+use crate::settings_state::SettingsState;
+use crate::rendering::row_spacing;
+use crate::rendering::target::DrawTarget;
+use crate::rendering::{text_renderer::TextRenderer, theme::Theme};
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas};
+use std::cell::Cell;
+
+/// Lists stop this far above the bottom edge, clear of the controls hint
+const LIST_BOTTOM_MARGIN: i32 = 90;
+
+/// How far a newly selected item jumps before bouncing back into place
+const BOUNCE_PIXELS: f32 = 10.0;
+const BOUNCE_SECONDS: f32 = 0.35;
 
 pub struct MenuRenderer {
     text_renderer: TextRenderer,
     window_width: u32,
     window_height: u32,
+    theme: Theme,
+    locale: Locale,
+    reduced_motion: bool,
+    /// Selected index and bounce of the main menu and the settings list
+    menu_bounce: Cell<Option<(usize, Tween)>>,
+    settings_bounce: Cell<Option<(usize, Tween)>>,
 }
 
 impl MenuRenderer {
@@ -17,41 +38,102 @@ impl MenuRenderer {
             text_renderer,
             window_width,
             window_height,
+            theme: Theme::default(),
+            locale: Locale::default(),
+            reduced_motion: false,
+            menu_bounce: Cell::new(None),
+            settings_bounce: Cell::new(None),
+        }
+    }
+
+    pub fn resize(&mut self, window_width: u32, window_height: u32) {
+        self.window_width = window_width;
+        self.window_height = window_height;
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.reduced_motion = reduced_motion;
+    }
+
+    /// Vertical offset of the selected item, bouncing whenever the selection moves
+    fn bounce_offset(&self, bounce: &Cell<Option<(usize, Tween)>>, selected: usize, now: f32) -> i32 {
+        let tween = match bounce.get() {
+            Some((index, tween)) if index == selected => tween,
+            // The first selection after opening a screen sits still
+            previous => {
+                let tween = if previous.is_some() && !self.reduced_motion {
+                    Tween::new(-BOUNCE_PIXELS, 0.0, now, BOUNCE_SECONDS, Easing::BounceOut)
+                } else {
+                    Tween::at(0.0)
+                };
+                bounce.set(Some((selected, tween)));
+                tween
+            }
+        };
+        tween.value(now).round() as i32
+    }
+
+    /// Cover the new screen with the background while a transition plays out
+    pub fn render_transition<T: DrawTarget>(&self, canvas: &mut Canvas<T>, transition: &Transition, clock: &Clock) {
+        let progress = transition.progress(clock.now());
+        let background = self.theme.background.0;
+        let (width, height) = (self.window_width, self.window_height);
+
+        match transition.kind {
+            TransitionKind::Fade => {
+                let alpha = ((1.0 - progress) * 255.0) as u8;
+                canvas.set_blend_mode(BlendMode::Blend);
+                canvas.set_draw_color(Color::RGBA(background.r, background.g, background.b, alpha));
+                canvas.fill_rect(Rect::new(0, 0, width, height)).ok();
+            }
+            TransitionKind::SlideLeft | TransitionKind::SlideRight => {
+                let travel = (progress * width as f32) as i32;
+                let x = if transition.kind == TransitionKind::SlideLeft { -travel } else { travel };
+                canvas.set_draw_color(background);
+                canvas.fill_rect(Rect::new(x, 0, width, height)).ok();
+            }
         }
     }
 
-    pub fn render(&self, canvas: &mut Canvas<Window>, font: &Font, menu: &MenuState) {
-        canvas.set_draw_color(colors::BACKGROUND);
+    pub fn render<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, menu: &MenuState, clock: &Clock) {
+        canvas.set_draw_color(self.theme.background.0);
         canvas.clear();
 
         // Title
         self.text_renderer.draw_text_centered(
             canvas,
-            font,
-            "NEON SNAKE",
+            fonts,
+            FontSize::Large,
+            self.locale.get("menu.title"),
             (self.window_width / 2) as i32,
             (self.window_height / 4) as i32,
-            colors::TEXT,
+            self.theme.text.0,
         ).ok();
 
         // Menu items
         let items = MenuState::get_items();
-        let start_y = self.window_height / 2;
-        let spacing = 50;
+        let start_y = (self.window_height * 2 / 5) as i32;
+        let spacing = row_spacing(start_y, self.window_height as i32 - LIST_BOTTOM_MARGIN, items.len(), 50);
+        let selected = items.iter().position(|item| *item == menu.selected_item).unwrap_or(0);
+        let bounce = self.bounce_offset(&self.menu_bounce, selected, clock.now());
 
         for (i, item) in items.iter().enumerate() {
-            let y = start_y + (i as u32 * spacing);
-            let is_selected = *item == menu.selected_item;
-            let text = match item {
-                MenuItem::Start => "START",
-                MenuItem::Leaderboard => "LEADERBOARD",
-                MenuItem::Exit => "EXIT",
-            };
+            let is_selected = i == selected;
+            let y = start_y + i as i32 * spacing + if is_selected { bounce } else { 0 };
+            let text = self.locale.get(item.label_key());
 
             let color = if is_selected {
-                colors::TEXT
+                self.theme.text.0
             } else {
-                colors::TEXT_DIM
+                self.theme.text_dim.0
             };
 
             let display_text = if is_selected {
@@ -62,10 +144,11 @@ impl MenuRenderer {
 
             self.text_renderer.draw_text_centered(
                 canvas,
-                font,
+                fonts,
+                FontSize::Normal,
                 &display_text,
                 (self.window_width / 2) as i32,
-                y as i32,
+                y,
                 color,
             ).ok();
         }
@@ -73,13 +156,135 @@ impl MenuRenderer {
         // Controls hint
         self.text_renderer.draw_text_centered(
             canvas,
-            font,
-            "[Arrow Keys / D-Pad] Navigate | [Enter / A] Select | [ESC] Exit",
+            fonts,
+            FontSize::Small,
+            self.locale.get("menu.hint"),
+            (self.window_width / 2) as i32,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    pub fn render_settings<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        settings: &SettingsState,
+        clock: &Clock,
+    ) {
+        canvas.set_draw_color(self.theme.background.0);
+        canvas.clear();
+
+        // Title
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get("settings.title"),
             (self.window_width / 2) as i32,
-            (self.window_height - 50) as i32,
-            colors::TEXT_DIM,
+            100,
+            self.theme.text.0,
         ).ok();
 
-        canvas.present();
+        // Settings items with their current values
+        let items = SettingsState::get_items();
+        let start_y = 160;
+        let spacing = row_spacing(start_y, self.window_height as i32 - LIST_BOTTOM_MARGIN, items.len(), 40);
+        let selected = items.iter().position(|item| *item == settings.selected_item).unwrap_or(0);
+        let bounce = self.bounce_offset(&self.settings_bounce, selected, clock.now());
+
+        for (i, item) in items.iter().enumerate() {
+            let is_selected = i == selected;
+            let y = start_y + i as i32 * spacing + if is_selected { bounce } else { 0 };
+            let label = self.locale.get(item.label_key());
+
+            let text = match settings.value_label(*item, &self.locale) {
+                Some(value) if is_selected => format!("{}: < {} >", label, value),
+                Some(value) => format!("{}: {}", label, value),
+                None if is_selected => format!("> {} <", label),
+                None => label.to_string(),
+            };
+
+            let color = if is_selected {
+                self.theme.text.0
+            } else {
+                self.theme.text_dim.0
+            };
+
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                FontSize::Normal,
+                &text,
+                (self.window_width / 2) as i32,
+                y,
+                color,
+            ).ok();
+        }
+
+        // Controls hint
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("settings.hint"),
+            (self.window_width / 2) as i32,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
+    }
+
+    pub fn render_credits<T: DrawTarget>(&self, canvas: &mut Canvas<T>, fonts: &FontSystem, credits: &CreditsState) {
+        canvas.set_draw_color(self.theme.background.0);
+        canvas.clear();
+
+        // Title
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Large,
+            self.locale.get("credits.title"),
+            (self.window_width / 2) as i32,
+            60,
+            self.theme.text.0,
+        ).ok();
+
+        // Attribution lines, with a gap before each pack heading, squeezed to fit the window
+        let top = 120;
+        let headings = credits.lines.iter().filter(|line| line.is_heading).count();
+        let rows = credits.lines.len() + headings;
+        let spacing = row_spacing(top, self.window_height as i32 - LIST_BOTTOM_MARGIN, rows, 30);
+        let mut y = top;
+        for line in &credits.lines {
+            if line.is_heading && y > top {
+                y += spacing * 2 / 3;
+            }
+            let (color, size) = if line.is_heading {
+                (self.theme.highlight.0, FontSize::Normal)
+            } else {
+                (self.theme.text_dim.0, FontSize::Small)
+            };
+            self.text_renderer.draw_text_centered(
+                canvas,
+                fonts,
+                size,
+                &line.text,
+                (self.window_width / 2) as i32,
+                y,
+                color,
+            ).ok();
+            y += spacing;
+        }
+
+        // Controls hint
+        self.text_renderer.draw_text_centered(
+            canvas,
+            fonts,
+            FontSize::Small,
+            self.locale.get("credits.hint"),
+            (self.window_width / 2) as i32,
+            self.window_height as i32 - 50,
+            self.theme.text_dim.0,
+        ).ok();
     }
 }
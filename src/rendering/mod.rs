@@ -1,7 +1,19 @@
-pub mod colors;
 pub mod game_renderer;
+#[cfg(test)]
+mod golden_tests;
 pub mod menu_renderer;
+pub mod particles;
+pub mod post_fx;
+pub mod sprites;
+pub mod target;
 pub mod text_renderer;
+pub mod theme;
 
 pub use game_renderer::GameRenderer;
 pub use menu_renderer::MenuRenderer;
+pub use theme::ThemeLibrary;
+
+/// Gap between `rows` evenly spaced rows that fit from `top` down to `bottom`, at most `max`
+pub fn row_spacing(top: i32, bottom: i32, rows: usize, max: i32) -> i32 {
+    ((bottom - top) / rows.max(1) as i32).clamp(1, max)
+}
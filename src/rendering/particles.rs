@@ -0,0 +1,174 @@
+// Particles - short-lived coloured squares for eat, death and milestone effects
+use rand::RngExt;
+use sdl2::pixels::Color;
+
+/// Hard cap so a long death burst can never swamp the software renderer
+const MAX_PARTICLES: usize = 600;
+
+/// Particles per food eaten at density 1.0
+const FOOD_PARTICLES: f32 = 16.0;
+
+/// Particles per body segment when the snake bursts apart at density 1.0
+const DEATH_PARTICLES_PER_SEGMENT: f32 = 6.0;
+
+/// Delay between neighbouring segments bursting, head first
+pub const DEATH_SEGMENT_DELAY: f32 = 0.04;
+
+/// Particles in a power-up or milestone ring at density 1.0
+const RING_PARTICLES: f32 = 24.0;
+
+/// Fraction of velocity kept per second, so bursts slow as they fade
+const DRAG_PER_SECOND: f32 = 0.08;
+
+/// One particle, positioned in grid cells so it survives a window resize
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    vx: f32,
+    vy: f32,
+    /// Seconds before the particle appears
+    delay: f32,
+    age: f32,
+    lifetime: f32,
+    /// Edge length in cells
+    pub size: f32,
+    color: Color,
+}
+
+impl Particle {
+    /// Colour with alpha fading out over the particle's lifetime
+    pub fn color(&self) -> Color {
+        let remaining = 1.0 - (self.age / self.lifetime).clamp(0.0, 1.0);
+        Color::RGBA(self.color.r, self.color.g, self.color.b, (self.color.a as f32 * remaining) as u8)
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.delay <= 0.0
+    }
+}
+
+/// ParticleSystem - owns live particles and the emitters that spawn them
+///
+/// `density` scales how many particles each emitter spawns; 0 spawns none.
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    density: f32,
+}
+
+impl ParticleSystem {
+    pub fn new(density: f32) -> Self {
+        ParticleSystem {
+            particles: Vec::new(),
+            density: density.max(0.0),
+        }
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    /// Advance every particle by `dt` seconds and drop the ones that have faded
+    pub fn update(&mut self, dt: f32) {
+        let drag = DRAG_PER_SECOND.powf(dt);
+        for p in &mut self.particles {
+            if p.delay > 0.0 {
+                p.delay -= dt;
+                continue;
+            }
+            p.age += dt;
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            p.vx *= drag;
+            p.vy *= drag;
+        }
+        self.particles.retain(|p| p.age < p.lifetime);
+    }
+
+    /// Burst of sparks from the centre of the cell where food was eaten
+    pub fn emit_food(&mut self, x: i32, y: i32, color: Color) {
+        let count = self.scaled(FOOD_PARTICLES);
+        for _ in 0..count {
+            self.spawn_burst(x, y, color, 0.0, 3.0..8.0, 0.3..0.6);
+        }
+    }
+
+    /// The body bursting apart one segment at a time, head first
+    ///
+    /// `segments` pairs each body cell with the colour it was drawn in.
+    pub fn emit_death(&mut self, segments: impl Iterator<Item = (i32, i32, Color)>) {
+        let count = self.scaled(DEATH_PARTICLES_PER_SEGMENT);
+        for (i, (x, y, color)) in segments.enumerate() {
+            let delay = i as f32 * DEATH_SEGMENT_DELAY;
+            for _ in 0..count {
+                self.spawn_burst(x, y, color, delay, 1.5..5.0, 0.5..1.0);
+            }
+        }
+    }
+
+    /// Expanding ring around a power-up as it is collected, or the head on a cleared board
+    pub fn emit_power_up(&mut self, x: i32, y: i32, color: Color) {
+        self.spawn_ring(x, y, color, 5.0);
+    }
+
+    /// Wide, fast ring around the head when the snake reaches a new speed milestone
+    pub fn emit_speed_milestone(&mut self, x: i32, y: i32, color: Color) {
+        self.spawn_ring(x, y, color, 9.0);
+    }
+
+    fn scaled(&self, base: f32) -> usize {
+        (base * self.density).round() as usize
+    }
+
+    fn spawn_burst(
+        &mut self,
+        x: i32,
+        y: i32,
+        color: Color,
+        delay: f32,
+        speed: std::ops::Range<f32>,
+        lifetime: std::ops::Range<f32>,
+    ) {
+        let mut rng = rand::rng();
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let speed = rng.random_range(speed);
+        self.push(Particle {
+            x: x as f32 + rng.random_range(0.3..0.7),
+            y: y as f32 + rng.random_range(0.3..0.7),
+            vx: angle.cos() * speed,
+            vy: angle.sin() * speed,
+            delay,
+            age: 0.0,
+            lifetime: rng.random_range(lifetime),
+            size: rng.random_range(0.12..0.25),
+            color,
+        });
+    }
+
+    fn spawn_ring(&mut self, x: i32, y: i32, color: Color, speed: f32) {
+        let count = self.scaled(RING_PARTICLES);
+        for i in 0..count {
+            let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+            self.push(Particle {
+                x: x as f32 + 0.5,
+                y: y as f32 + 0.5,
+                vx: angle.cos() * speed,
+                vy: angle.sin() * speed,
+                delay: 0.0,
+                age: 0.0,
+                lifetime: 0.5,
+                size: 0.2,
+                color,
+            });
+        }
+    }
+
+    fn push(&mut self, particle: Particle) {
+        if self.particles.len() < MAX_PARTICLES {
+            self.particles.push(particle);
+        }
+    }
+}
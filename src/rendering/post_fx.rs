@@ -0,0 +1,284 @@
+// Post-processing - glow halos, CRT scanlines, screen shake and chromatic flash
+//
+// Every effect is built from plain texture copies with blend and colour
+// modulation, so they all run on SDL's software renderer.
+use crate::config::VisualConfig;
+use crate::rendering::target::{DrawTarget, OwnedTexture};
+use rand::RngExt;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas, Texture, TextureCreator};
+use std::cell::RefCell;
+
+/// Edge length of the radial glow sprite; it is stretched to size when drawn
+const GLOW_TEXTURE_SIZE: u32 = 64;
+
+/// Halo diameter as a multiple of the cell size
+pub const GLOW_SCALE: f32 = 2.6;
+
+/// Every third row of the CRT overlay is darkened
+const SCANLINE_PERIOD: u32 = 3;
+const SCANLINE_ALPHA: f32 = 70.0;
+
+/// Darkening at the very corners of the CRT overlay
+const VIGNETTE_ALPHA: f32 = 110.0;
+
+const SHAKE_SECONDS: f32 = 0.4;
+const SHAKE_PIXELS: f32 = 8.0;
+
+const CHROMATIC_SECONDS: f32 = 0.25;
+const CHROMATIC_PIXELS: f32 = 5.0;
+const CHROMATIC_ALPHA: f32 = 110.0;
+
+/// Which post-processing effects are switched on
+#[derive(Debug, Clone, Copy)]
+pub struct PostEffectsConfig {
+    pub glow: bool,
+    pub scanlines: bool,
+    pub screen_shake: bool,
+    pub chromatic_flash: bool,
+}
+
+impl PostEffectsConfig {
+    pub fn from_visual(visual: &VisualConfig) -> Self {
+        PostEffectsConfig {
+            glow: visual.glow,
+            scanlines: visual.scanlines,
+            screen_shake: visual.screen_shake,
+            chromatic_flash: visual.chromatic_flash,
+        }
+    }
+}
+
+/// A texture created on first use and rebuilt when its size no longer fits
+struct CachedTexture {
+    texture: OwnedTexture,
+    size: (u32, u32),
+}
+
+/// PostEffects - owns the offscreen playfield and the effect textures
+pub struct PostEffects {
+    config: PostEffectsConfig,
+    /// Motion effects are held still for reduced motion
+    reduced_motion: bool,
+    offscreen: RefCell<Option<CachedTexture>>,
+    glow: RefCell<Option<CachedTexture>>,
+    crt_overlay: RefCell<Option<CachedTexture>>,
+    /// Remaining shake and chromatic flash, from 1.0 down to 0.0
+    shake: f32,
+    chromatic: f32,
+}
+
+impl PostEffects {
+    pub fn new(config: PostEffectsConfig) -> Self {
+        PostEffects {
+            config,
+            reduced_motion: false,
+            offscreen: RefCell::new(None),
+            glow: RefCell::new(None),
+            crt_overlay: RefCell::new(None),
+            shake: 0.0,
+            chromatic: 0.0,
+        }
+    }
+
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.reduced_motion = reduced_motion;
+        if reduced_motion {
+            self.shake = 0.0;
+            self.chromatic = 0.0;
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.shake = (self.shake - dt / SHAKE_SECONDS).max(0.0);
+        self.chromatic = (self.chromatic - dt / CHROMATIC_SECONDS).max(0.0);
+    }
+
+    pub fn on_food_eaten(&mut self) {
+        if self.config.chromatic_flash && !self.reduced_motion {
+            self.chromatic = 1.0;
+        }
+    }
+
+    pub fn on_death(&mut self) {
+        if self.config.screen_shake && !self.reduced_motion {
+            self.shake = 1.0;
+        }
+    }
+
+    /// Shake and the chromatic flash need the playfield as a texture; the rest draw directly
+    fn needs_offscreen(&self) -> bool {
+        self.config.screen_shake || self.config.chromatic_flash
+    }
+
+    /// Draw the playfield with `draw`, offscreen when an effect needs it, then put it on screen
+    pub fn render_scene<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        background: Color,
+        draw: impl FnOnce(&mut Canvas<T>),
+    ) {
+        if !self.needs_offscreen() {
+            draw(canvas);
+            return;
+        }
+
+        let size = canvas.output_size().unwrap_or((1, 1));
+        let mut offscreen = self.offscreen.borrow_mut();
+        let target = match ensure_texture(canvas, &mut offscreen, size, |creator, (w, h)| {
+            creator
+                .create_texture_target(PixelFormatEnum::ARGB8888, w, h)
+                .map_err(|e| e.to_string())
+        }) {
+            Ok(target) => target,
+            Err(e) => {
+                // No render targets on this renderer: effects off, playfield still drawn
+                eprintln!("Offscreen rendering unavailable: {}", e);
+                draw(canvas);
+                return;
+            }
+        };
+
+        if let Err(e) = canvas.with_texture_canvas(target, draw) {
+            eprintln!("Offscreen rendering failed: {}", e);
+            return;
+        }
+
+        // Shake moves the whole playfield by a random offset that settles as it fades
+        let (mut dx, mut dy) = (0, 0);
+        if self.shake > 0.0 {
+            let mut rng = rand::rng();
+            let reach = SHAKE_PIXELS * self.shake;
+            dx = rng.random_range(-reach..=reach).round() as i32;
+            dy = rng.random_range(-reach..=reach).round() as i32;
+        }
+
+        canvas.set_draw_color(background);
+        canvas.clear();
+        let dest = Rect::new(dx, dy, size.0, size.1);
+        target.set_blend_mode(BlendMode::None);
+        target.set_color_mod(255, 255, 255);
+        target.set_alpha_mod(255);
+        canvas.copy(target, None, dest).ok();
+
+        // Chromatic flash adds red and blue copies pulled apart horizontally
+        if self.chromatic > 0.0 {
+            let spread = (CHROMATIC_PIXELS * self.chromatic).round() as i32;
+            target.set_blend_mode(BlendMode::Add);
+            target.set_alpha_mod((CHROMATIC_ALPHA * self.chromatic) as u8);
+            for (color, offset) in [((255, 0, 60), -spread), ((60, 0, 255), spread)] {
+                target.set_color_mod(color.0, color.1, color.2);
+                canvas.copy(target, None, Rect::new(dx + offset, dy, size.0, size.1)).ok();
+            }
+        }
+    }
+
+    /// Soft additive halo of `color` centred on `center`
+    pub fn draw_glow<T: DrawTarget>(&self, canvas: &mut Canvas<T>, center: (i32, i32), diameter: u32, color: Color) {
+        if !self.config.glow {
+            return;
+        }
+
+        let mut glow = self.glow.borrow_mut();
+        let size = (GLOW_TEXTURE_SIZE, GLOW_TEXTURE_SIZE);
+        let Ok(texture) = ensure_texture(canvas, &mut glow, size, |creator, (w, h)| {
+            let mut texture = creator
+                .create_texture_static(PixelFormatEnum::ARGB8888, w, h)
+                .map_err(|e| e.to_string())?;
+            texture
+                .update(None, &glow_pixels(w), (w * 4) as usize)
+                .map_err(|e| e.to_string())?;
+            texture.set_blend_mode(BlendMode::Add);
+            Ok(texture)
+        }) else {
+            return;
+        };
+
+        texture.set_color_mod(color.r, color.g, color.b);
+        texture.set_alpha_mod(color.a);
+        let half = diameter as i32 / 2;
+        canvas
+            .copy(texture, None, Rect::new(center.0 - half, center.1 - half, diameter, diameter))
+            .ok();
+    }
+
+    /// Scanlines and a vignette over the whole frame
+    pub fn draw_crt_overlay<T: DrawTarget>(&self, canvas: &mut Canvas<T>) {
+        if !self.config.scanlines {
+            return;
+        }
+
+        let size = canvas.output_size().unwrap_or((1, 1));
+        let mut overlay = self.crt_overlay.borrow_mut();
+        let Ok(texture) = ensure_texture(canvas, &mut overlay, size, |creator, (w, h)| {
+            let mut texture = creator
+                .create_texture_static(PixelFormatEnum::ARGB8888, w, h)
+                .map_err(|e| e.to_string())?;
+            texture
+                .update(None, &crt_pixels(w, h), (w * 4) as usize)
+                .map_err(|e| e.to_string())?;
+            texture.set_blend_mode(BlendMode::Blend);
+            Ok(texture)
+        }) else {
+            return;
+        };
+
+        canvas.copy(texture, None, None).ok();
+    }
+}
+
+/// Reuse the cached texture if it has the right size, otherwise rebuild it
+fn ensure_texture<'a, T: DrawTarget>(
+    canvas: &Canvas<T>,
+    cache: &'a mut Option<CachedTexture>,
+    size: (u32, u32),
+    create: impl FnOnce(&TextureCreator<T::Context>, (u32, u32)) -> Result<Texture, String>,
+) -> Result<&'a mut Texture, String> {
+    if cache.as_ref().is_some_and(|c| c.size != size) {
+        *cache = None;
+    }
+    if cache.is_none() {
+        let texture = OwnedTexture::new(create(&T::texture_creator(canvas), size)?);
+        *cache = Some(CachedTexture { texture, size });
+    }
+    Ok(&mut cache.as_mut().expect("texture was just created").texture)
+}
+
+/// White square fading to transparent from the centre outwards, in ARGB8888
+fn glow_pixels(size: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    let radius = size as f32 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = (x as f32 + 0.5 - radius) / radius;
+            let dy = (y as f32 + 0.5 - radius) / radius;
+            let falloff = (1.0 - (dx * dx + dy * dy).sqrt()).max(0.0);
+            let alpha = (falloff * falloff * 255.0) as u8;
+            pixels.extend_from_slice(&argb(alpha, 255, 255, 255));
+        }
+    }
+    pixels
+}
+
+/// Dark scanlines plus a vignette that deepens towards the corners, in ARGB8888
+fn crt_pixels(width: u32, height: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+    let (half_w, half_h) = (width as f32 / 2.0, height as f32 / 2.0);
+    for y in 0..height {
+        let scanline = if y % SCANLINE_PERIOD == SCANLINE_PERIOD - 1 { SCANLINE_ALPHA } else { 0.0 };
+        let ny = (y as f32 - half_h) / half_h;
+        for x in 0..width {
+            let nx = (x as f32 - half_w) / half_w;
+            let vignette = ((nx * nx + ny * ny) / 2.0).powi(2) * VIGNETTE_ALPHA;
+            let alpha = (scanline + vignette).min(255.0) as u8;
+            pixels.extend_from_slice(&argb(alpha, 0, 0, 0));
+        }
+    }
+    pixels
+}
+
+/// One ARGB8888 pixel in native byte order
+fn argb(a: u8, r: u8, g: u8, b: u8) -> [u8; 4] {
+    u32::from_be_bytes([a, r, g, b]).to_ne_bytes()
+}
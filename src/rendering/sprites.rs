@@ -0,0 +1,136 @@
+// Sprites - tilesheet-based drawing for themes that ship artwork
+//
+// Tilesheet layout, one tile per cell of a 4-column grid:
+//
+//   row 0: head moving up, right, down, left
+//   row 1: tail moving up, right, down, left (the next segment lies that way)
+//   row 2: horizontal body, vertical body, wall, unused
+//   row 3: corners joining up+right, right+down, down+left, left+up
+//   row 4: food variants, left to right
+use crate::game_state::{Direction, Position};
+use crate::rendering::target::OwnedTexture;
+use crate::rendering::theme::TilesetSpec;
+use sdl2::rect::Rect;
+use sdl2::render::Texture;
+
+const COLUMNS: u32 = 4;
+const ROWS: u32 = 5;
+
+/// One tile of the sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Head(Direction),
+    Tail(Direction),
+    Horizontal,
+    Vertical,
+    /// Body piece joining two perpendicular sides
+    Corner(Direction, Direction),
+    Wall,
+    Food(u32),
+}
+
+/// Tileset - a loaded tilesheet texture and its layout
+pub struct Tileset {
+    texture: OwnedTexture,
+    tile_size: u32,
+    food_variants: u32,
+}
+
+impl Tileset {
+    pub fn new(texture: Texture, spec: &TilesetSpec) -> Result<Self, String> {
+        let texture = OwnedTexture::new(texture);
+        let query = texture.query();
+        if spec.tile_size == 0 || query.width < spec.tile_size * COLUMNS || query.height < spec.tile_size * ROWS {
+            return Err(format!(
+                "Tilesheet {} is {}x{}, expected at least {}x{} for {}px tiles",
+                spec.image,
+                query.width,
+                query.height,
+                spec.tile_size * COLUMNS,
+                spec.tile_size * ROWS,
+                spec.tile_size
+            ));
+        }
+
+        Ok(Tileset {
+            texture,
+            tile_size: spec.tile_size,
+            food_variants: spec.food_variants.clamp(1, COLUMNS),
+        })
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Food tile for a food at `pos`, so each new food can look different
+    pub fn food_tile(&self, pos: Position) -> Tile {
+        Tile::Food((pos.x * 7 + pos.y * 13).rem_euclid(self.food_variants as i32) as u32)
+    }
+
+    /// Where a tile sits on the sheet
+    pub fn source_rect(&self, tile: Tile) -> Rect {
+        let (column, row) = match tile {
+            Tile::Head(direction) => (direction_column(direction), 0),
+            Tile::Tail(direction) => (direction_column(direction), 1),
+            Tile::Horizontal => (0, 2),
+            Tile::Vertical => (1, 2),
+            Tile::Wall => (2, 2),
+            Tile::Corner(a, b) => (corner_column(a, b), 3),
+            Tile::Food(variant) => (variant % self.food_variants, 4),
+        };
+        Rect::new(
+            (column * self.tile_size) as i32,
+            (row * self.tile_size) as i32,
+            self.tile_size,
+            self.tile_size,
+        )
+    }
+}
+
+fn direction_column(direction: Direction) -> u32 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+    }
+}
+
+fn corner_column(a: Direction, b: Direction) -> u32 {
+    use Direction::*;
+    match (a, b) {
+        (Up, Right) | (Right, Up) => 0,
+        (Right, Down) | (Down, Right) => 1,
+        (Down, Left) | (Left, Down) => 2,
+        _ => 3,
+    }
+}
+
+/// Direction from a cell to an orthogonally adjacent one
+pub fn side(from: Position, to: Position) -> Option<Direction> {
+    match (to.x - from.x, to.y - from.y) {
+        (0, -1) => Some(Direction::Up),
+        (0, 1) => Some(Direction::Down),
+        (-1, 0) => Some(Direction::Left),
+        (1, 0) => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+/// Body tile for `cell` given its neighbours towards the head and towards the tail
+pub fn body_tile(towards_head: Position, cell: Position, towards_tail: Position) -> Tile {
+    match (side(cell, towards_head), side(cell, towards_tail)) {
+        (Some(a), Some(b)) if a == b.opposite() => match a {
+            Direction::Left | Direction::Right => Tile::Horizontal,
+            Direction::Up | Direction::Down => Tile::Vertical,
+        },
+        (Some(a), Some(b)) if a != b => Tile::Corner(a, b),
+        // Only one usable neighbour: a straight piece along it
+        (Some(d), _) | (_, Some(d)) => match d {
+            Direction::Left | Direction::Right => Tile::Horizontal,
+            Direction::Up | Direction::Down => Tile::Vertical,
+        },
+        (None, None) => Tile::Horizontal,
+    }
+}
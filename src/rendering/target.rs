@@ -0,0 +1,63 @@
+// Render targets - what the renderers can draw to
+//
+// The renderers are generic over the canvas they draw to, so the same code
+// draws to the game window or to a surface in memory.
+use sdl2::render::{Canvas, RenderTarget, Texture, TextureCreator};
+use std::ops::{Deref, DerefMut};
+use sdl2::surface::Surface;
+use sdl2::video::Window;
+
+/// DrawTarget - a window or an in-memory surface the renderers can draw to
+///
+/// Textures are tied to the canvas whose creator made them, so a renderer
+/// should keep drawing to the same canvas.
+pub trait DrawTarget: RenderTarget + Sized {
+    fn texture_creator(canvas: &Canvas<Self>) -> TextureCreator<Self::Context>;
+}
+
+impl DrawTarget for Window {
+    fn texture_creator(canvas: &Canvas<Self>) -> TextureCreator<Self::Context> {
+        canvas.texture_creator()
+    }
+}
+
+impl<'s> DrawTarget for Surface<'s> {
+    fn texture_creator(canvas: &Canvas<Self>) -> TextureCreator<Self::Context> {
+        canvas.texture_creator()
+    }
+}
+
+/// OwnedTexture - a texture that is destroyed when dropped
+///
+/// With `unsafe_textures` SDL textures are never freed on their own. The owner
+/// must be dropped before the canvas that created the texture.
+pub struct OwnedTexture(Option<Texture>);
+
+impl OwnedTexture {
+    pub fn new(texture: Texture) -> Self {
+        OwnedTexture(Some(texture))
+    }
+}
+
+impl Deref for OwnedTexture {
+    type Target = Texture;
+
+    fn deref(&self) -> &Texture {
+        self.0.as_ref().expect("texture is only taken on drop")
+    }
+}
+
+impl DerefMut for OwnedTexture {
+    fn deref_mut(&mut self) -> &mut Texture {
+        self.0.as_mut().expect("texture is only taken on drop")
+    }
+}
+
+impl Drop for OwnedTexture {
+    fn drop(&mut self) {
+        if let Some(texture) = self.0.take() {
+            // SAFETY: owners are dropped before the canvas that created the texture
+            unsafe { texture.destroy() };
+        }
+    }
+}
@@ -1,71 +1,341 @@
-use sdl2::pixels::Color;
+use crate::fonts::{FontSize, FontSystem};
+use crate::rendering::target::{DrawTarget, OwnedTexture};
+use sdl2::pixels::{Color, PixelFormatEnum};
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
+use sdl2::render::{BlendMode, Canvas};
 use sdl2::ttf::Font;
-use sdl2::video::Window;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
-/// Text renderer using SDL2_ttf for real text rendering
-pub struct TextRenderer;
+/// Width and height of each glyph atlas texture
+const ATLAS_SIZE: u32 = 512;
+
+/// Space kept clear on each side when centred text is shrunk to fit the screen
+const TEXT_MARGIN: u32 = 16;
+
+/// GlyphAtlas - glyphs of one font rasterized once, in white, into a shared texture
+///
+/// Glyphs are packed left to right in rows as they are first used.
+struct GlyphAtlas {
+    texture: OwnedTexture,
+    glyphs: HashMap<char, Rect>,
+    cursor_x: u32,
+    cursor_y: u32,
+    row_height: u32,
+}
+
+impl GlyphAtlas {
+    fn new<T: DrawTarget>(canvas: &Canvas<T>) -> Result<Self, String> {
+        let mut texture = T::texture_creator(canvas)
+            .create_texture_static(PixelFormatEnum::ARGB8888, ATLAS_SIZE, ATLAS_SIZE)
+            .map_err(|e| format!("Failed to create glyph atlas: {}", e))?;
+        texture.set_blend_mode(BlendMode::Blend);
+
+        Ok(GlyphAtlas {
+            texture: OwnedTexture::new(texture),
+            glyphs: HashMap::new(),
+            cursor_x: 0,
+            cursor_y: 0,
+            row_height: 0,
+        })
+    }
+
+    /// Forget every glyph so the texture space can be reused
+    fn clear(&mut self) {
+        self.glyphs.clear();
+        self.cursor_x = 0;
+        self.cursor_y = 0;
+        self.row_height = 0;
+    }
+
+    /// Atlas location of a glyph, rasterizing it on first use
+    ///
+    /// Returns `Ok(None)` when the atlas has no room left.
+    fn glyph(&mut self, font: &Font, ch: char) -> Result<Option<Rect>, String> {
+        if let Some(rect) = self.glyphs.get(&ch) {
+            return Ok(Some(*rect));
+        }
+
+        let surface = font
+            .render_char(ch)
+            .blended(Color::WHITE)
+            .map_err(|e| format!("Failed to render glyph {:?}: {}", ch, e))?
+            .convert_format(PixelFormatEnum::ARGB8888)?;
+        let (width, height) = (surface.width(), surface.height());
+
+        if self.cursor_x + width > ATLAS_SIZE {
+            self.cursor_x = 0;
+            self.cursor_y += self.row_height;
+            self.row_height = 0;
+        }
+        if self.cursor_y + height > ATLAS_SIZE || width > ATLAS_SIZE {
+            return Ok(None);
+        }
+
+        let rect = Rect::new(self.cursor_x as i32, self.cursor_y as i32, width, height);
+        let pitch = surface.pitch() as usize;
+        surface
+            .with_lock(|pixels| self.texture.update(rect, pixels, pitch))
+            .map_err(|e| format!("Failed to upload glyph {:?}: {}", ch, e))?;
+
+        self.cursor_x += width;
+        self.row_height = self.row_height.max(height);
+        self.glyphs.insert(ch, rect);
+        Ok(Some(rect))
+    }
+}
+
+/// Text renderer using SDL2_ttf, with one glyph atlas per font size
+///
+/// Each glyph is rasterized once; drawing a string is a texture copy per
+/// character, tinted with the texture colour mod.
+pub struct TextRenderer {
+    atlases: RefCell<HashMap<FontSize, GlyphAtlas>>,
+    font_generation: Cell<Option<u64>>,
+}
 
 impl TextRenderer {
     /// Creates a new TextRenderer
     pub fn new() -> Self {
-        TextRenderer
+        TextRenderer {
+            atlases: RefCell::new(HashMap::new()),
+            font_generation: Cell::new(None),
+        }
+    }
+
+    /// Atlas source rects for each character of `text`, filling the atlas as needed
+    fn layout<T: DrawTarget>(
+        &self,
+        canvas: &Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
+        text: &str,
+    ) -> Result<Vec<Rect>, String> {
+        let mut atlases = self.atlases.borrow_mut();
+
+        // Fonts were reloaded: the old atlases hold the wrong glyphs
+        if self.font_generation.get() != Some(fonts.generation()) {
+            atlases.clear();
+            self.font_generation.set(Some(fonts.generation()));
+        }
+
+        let atlas = match atlases.entry(size) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(GlyphAtlas::new(canvas)?),
+        };
+
+        // A full atlas is cleared and the string laid out again from scratch
+        for attempt in 0..2 {
+            let mut rects = Vec::with_capacity(text.len());
+            let mut full = false;
+            for ch in text.chars() {
+                match atlas.glyph(fonts.for_char(size, ch), ch)? {
+                    Some(rect) => rects.push(rect),
+                    None => {
+                        full = true;
+                        break;
+                    }
+                }
+            }
+            if !full {
+                return Ok(rects);
+            }
+            if attempt == 0 {
+                atlas.clear();
+            }
+        }
+        Err(format!("Text does not fit in a glyph atlas: {:?}", text))
+    }
+
+    /// Width of `text` in pixels at the given size
+    pub fn text_width<T: DrawTarget>(
+        &self,
+        canvas: &Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
+        text: &str,
+    ) -> Result<u32, String> {
+        Ok(self.layout(canvas, fonts, size, text)?.iter().map(|r| r.width()).sum())
     }
 
     /// Draws text at the specified position
-    pub fn draw_text(
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text<T: DrawTarget>(
         &self,
-        canvas: &mut Canvas<Window>,
-        font: &Font,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
         text: &str,
         x: i32,
         y: i32,
         color: Color,
     ) -> Result<(), String> {
-        // Render text to surface
-        let surface = font
-            .render(text)
-            .blended(color)
-            .map_err(|e| format!("Failed to render text: {}", e))?;
+        self.draw_glyphs(canvas, fonts, size, text, x, y, 1.0, color)
+    }
+
+    /// Draws text scaled about its centre, for zooming titles
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_scaled<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
+        text: &str,
+        center_x: i32,
+        center_y: i32,
+        scale: f32,
+        color: Color,
+    ) -> Result<(), String> {
+        let full_width = self.text_width(canvas, fonts, size, text)?;
+        let scale = scale.min(fit_scale(canvas, full_width));
+        let text_width = full_width as f32 * scale;
+        let text_height = fonts.sized(size).height() as f32 * scale;
+        let x = center_x - (text_width / 2.0) as i32;
+        let y = center_y - (text_height / 2.0) as i32;
+        self.draw_glyphs(canvas, fonts, size, text, x, y, scale, color)
+    }
 
-        // Convert surface to texture
-        let texture_creator = canvas.texture_creator();
-        let texture = texture_creator
-            .create_texture_from_surface(&surface)
-            .map_err(|e| format!("Failed to create texture: {}", e))?;
+    #[allow(clippy::too_many_arguments)]
+    fn draw_glyphs<T: DrawTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
+        text: &str,
+        x: i32,
+        y: i32,
+        scale: f32,
+        color: Color,
+    ) -> Result<(), String> {
+        let glyphs = self.layout(canvas, fonts, size, text)?;
 
-        // Get texture dimensions
-        let query = texture.query();
-        let target = Rect::new(x, y, query.width, query.height);
+        let mut atlases = self.atlases.borrow_mut();
+        let atlas = atlases
+            .get_mut(&size)
+            .ok_or_else(|| "Glyph atlas missing".to_string())?;
+        atlas.texture.set_color_mod(color.r, color.g, color.b);
+        atlas.texture.set_alpha_mod(color.a);
 
-        // Draw texture to canvas
-        canvas
-            .copy(&texture, None, Some(target))
-            .map_err(|e| format!("Failed to copy texture: {}", e))?;
+        // Copy each glyph from the atlas, advancing by its (scaled) width
+        let mut pen_x = x as f32;
+        for src in glyphs {
+            let width = (src.width() as f32 * scale).round().max(1.0) as u32;
+            let height = (src.height() as f32 * scale).round().max(1.0) as u32;
+            canvas
+                .copy(&atlas.texture, src, Rect::new(pen_x.round() as i32, y, width, height))
+                .map_err(|e| format!("Failed to copy glyph: {}", e))?;
+            pen_x += src.width() as f32 * scale;
+        }
 
         Ok(())
     }
 
     /// Draws text centered at the specified position
-    pub fn draw_text_centered(
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_centered<T: DrawTarget>(
         &self,
-        canvas: &mut Canvas<Window>,
-        font: &Font,
+        canvas: &mut Canvas<T>,
+        fonts: &FontSystem,
+        size: FontSize,
         text: &str,
         x: i32,
         y: i32,
         color: Color,
     ) -> Result<(), String> {
         // Get text dimensions
-        let (text_width, _) = font
-            .size_of(text)
-            .map_err(|e| format!("Failed to get text size: {}", e))?;
+        let text_width = self.text_width(canvas, fonts, size, text)?;
+
+        // Shrink text too wide for the screen (long translations), keeping its line centred
+        let scale = fit_scale(canvas, text_width);
+        if scale < 1.0 {
+            let height = fonts.sized(size).height() as f32;
+            let text_x = x - (text_width as f32 * scale / 2.0) as i32;
+            let text_y = y + (height * (1.0 - scale) / 2.0) as i32;
+            return self.draw_glyphs(canvas, fonts, size, text, text_x, text_y, scale, color);
+        }
 
         // Calculate centered x position
         let text_x = x - (text_width as i32 / 2);
 
         // Draw text
-        self.draw_text(canvas, font, text, text_x, y, color)
+        self.draw_text(canvas, fonts, size, text, text_x, y, color)
+    }
+}
+
+/// Scale that fits `text_width` pixels inside the canvas with a margin either side, at most 1
+fn fit_scale<T: DrawTarget>(canvas: &Canvas<T>, text_width: u32) -> f32 {
+    let available = canvas.output_size().map_or(0, |(w, _)| w).saturating_sub(2 * TEXT_MARGIN);
+    if text_width == 0 || available == 0 {
+        return 1.0;
+    }
+    (available as f32 / text_width as f32).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetResolver;
+    use sdl2::surface::Surface;
+    use std::time::{Duration, Instant};
+
+    /// Roughly the text on a settings screen
+    const LINES: [&str; 10] = [
+        "SETTINGS",
+        "Theme: Neon",
+        "Language: English",
+        "Text size: Normal",
+        "Color vision: Default",
+        "Reduced motion: Off",
+        "Sound: On",
+        "Music volume: 80%",
+        "Input queue: 3",
+        "ESC to go back",
+    ];
+    const FRAMES: u32 = 200;
+
+    fn time_frames(mut draw_frame: impl FnMut()) -> Duration {
+        draw_frame();
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            draw_frame();
+        }
+        start.elapsed() / FRAMES
+    }
+
+    /// Per-frame cost of the atlas against rasterizing every string each frame,
+    /// as the renderer did before, on the software renderer
+    ///
+    /// Run with `cargo test --release text_atlas_cpu_cost -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "timing benchmark"]
+    fn text_atlas_cpu_cost() {
+        let fonts = FontSystem::new(&AssetResolver::new(None), 16).expect("fonts load");
+        let mut canvas = Surface::new(800, 600, PixelFormatEnum::RGBA32)
+            .and_then(|s| s.into_canvas())
+            .expect("software canvas");
+        let creator = canvas.texture_creator();
+
+        let rasterized = time_frames(|| {
+            for (row, line) in LINES.iter().enumerate() {
+                let surface = fonts.font().render(line).blended(Color::WHITE).unwrap();
+                let texture = OwnedTexture::new(creator.create_texture_from_surface(&surface).unwrap());
+                let dst = Rect::new(20, row as i32 * 40, surface.width(), surface.height());
+                canvas.copy(&texture, None, dst).unwrap();
+            }
+        });
+
+        let renderer = TextRenderer::new();
+        let atlas = time_frames(|| {
+            for (row, line) in LINES.iter().enumerate() {
+                renderer
+                    .draw_text(&mut canvas, &fonts, FontSize::Normal, line, 20, row as i32 * 40, Color::WHITE)
+                    .unwrap();
+            }
+        });
+        drop(renderer);
+
+        println!("rasterized each frame: {:?}/frame, glyph atlas: {:?}/frame", rasterized, atlas);
+        assert!(atlas < rasterized, "the glyph atlas should be cheaper than rasterizing every frame");
     }
 }
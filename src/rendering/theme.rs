@@ -0,0 +1,188 @@
+// Themes - named colour sets loaded from TOML
+use crate::assets::AssetResolver;
+use sdl2::pixels::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Directory (inside an assets root) that holds user themes
+pub const THEMES_DIR: &str = "themes";
+
+/// Id of the theme used when nothing else is selected
+pub const DEFAULT_THEME: &str = "neon";
+
+/// Themes compiled into the binary, keyed by id
+const BUILTIN_THEMES: &[(&str, &str)] = &[
+    ("neon", include_str!("../../assets/themes/neon.toml")),
+    ("classic", include_str!("../../assets/themes/classic.toml")),
+    ("high_contrast", include_str!("../../assets/themes/high_contrast.toml")),
+    ("light", include_str!("../../assets/themes/light.toml")),
+    ("deuteranopia", include_str!("../../assets/themes/deuteranopia.toml")),
+    ("protanopia", include_str!("../../assets/themes/protanopia.toml")),
+    ("tritanopia", include_str!("../../assets/themes/tritanopia.toml")),
+    ("pixel", include_str!("../../assets/themes/pixel.toml")),
+];
+
+/// Colour written in TOML as `[r, g, b]` or `[r, g, b, a]`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "Vec<u8>")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<Vec<u8>> for ThemeColor {
+    type Error = String;
+
+    fn try_from(v: Vec<u8>) -> Result<Self, Self::Error> {
+        match v[..] {
+            [r, g, b] => Ok(ThemeColor(Color::RGB(r, g, b))),
+            [r, g, b, a] => Ok(ThemeColor(Color::RGBA(r, g, b, a))),
+            _ => Err(format!("expected [r, g, b] or [r, g, b, a], got {} values", v.len())),
+        }
+    }
+}
+
+/// Colours for each kind of food
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FoodColors {
+    pub normal: ThemeColor,
+}
+
+/// Tilesheet a theme draws the playfield with instead of flat cells
+///
+/// See `rendering::sprites` for the sheet layout.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TilesetSpec {
+    /// PNG path relative to an assets root
+    pub image: String,
+    /// Edge length of one tile in pixels
+    pub tile_size: u32,
+    /// How many food tiles the food row holds
+    #[serde(default = "default_food_variants")]
+    pub food_variants: u32,
+}
+
+fn default_food_variants() -> u32 {
+    1
+}
+
+/// Theme - every colour the renderers draw with
+///
+/// The snake body is a gradient from `snake_head` to `snake_tail`.
+/// Grid lines are drawn with `VisualConfig::grid_alpha` applied.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Theme {
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub background: ThemeColor,
+    pub grid: ThemeColor,
+    pub snake_head: ThemeColor,
+    pub snake_tail: ThemeColor,
+    pub wall: ThemeColor,
+    pub text: ThemeColor,
+    pub text_dim: ThemeColor,
+    pub highlight: ThemeColor,
+    pub food: FoodColors,
+    /// Optional sprites; colours still apply to the grid, text and effects
+    #[serde(default)]
+    pub tileset: Option<TilesetSpec>,
+}
+
+impl Theme {
+    pub fn parse(id: &str, text: &str) -> Result<Self, String> {
+        let mut theme: Theme = toml::from_str(text).map_err(|e| format!("Invalid theme '{}': {}", id, e))?;
+        theme.id = id.to_string();
+        Ok(theme)
+    }
+
+    fn load_file(path: &Path) -> Result<Self, String> {
+        let id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .ok_or_else(|| format!("Invalid theme path: {:?}", path))?;
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        Self::parse(&id, &text)
+    }
+
+    /// Colour of the body segment at `index` in a snake of `len` segments
+    pub fn snake_segment(&self, index: usize, len: usize) -> Color {
+        if len <= 1 {
+            return self.snake_head.0;
+        }
+        let t = index as f32 / (len - 1) as f32;
+        lerp_color(self.snake_head.0, self.snake_tail.0, t)
+    }
+
+    /// Copy of this theme with entries replaced by an asset pack's `[colors]` table
+    ///
+    /// `snake` sets both ends of the body gradient. Unknown names are ignored.
+    pub fn with_overrides(&self, overrides: &HashMap<String, [u8; 3]>) -> Self {
+        let mut theme = self.clone();
+        for (name, [r, g, b]) in overrides {
+            let color = ThemeColor(Color::RGB(*r, *g, *b));
+            match name.as_str() {
+                "background" => theme.background = color,
+                "grid" => theme.grid = color,
+                "snake" => {
+                    theme.snake_head = color;
+                    theme.snake_tail = color;
+                }
+                "snake_head" => theme.snake_head = color,
+                "snake_tail" => theme.snake_tail = color,
+                "food" => theme.food.normal = color,
+                "wall" => theme.wall = color,
+                "text" => theme.text = color,
+                "text_dim" => theme.text_dim = color,
+                "highlight" => theme.highlight = color,
+                _ => eprintln!("Unknown theme colour in asset pack: {}", name),
+            }
+        }
+        theme
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let (id, text) = BUILTIN_THEMES[0];
+        Theme::parse(id, text).expect("built-in theme is valid")
+    }
+}
+
+pub(crate) fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::RGBA(lerp(from.r, to.r), lerp(from.g, to.g), lerp(from.b, to.b), lerp(from.a, to.a))
+}
+
+/// ThemeLibrary - built-in themes plus any found under `<assets>/themes/`
+pub struct ThemeLibrary {
+    themes: Vec<Theme>,
+}
+
+impl ThemeLibrary {
+    pub fn load(assets: &AssetResolver) -> Self {
+        let builtins: Vec<Theme> = BUILTIN_THEMES
+            .iter()
+            .filter_map(|(id, text)| Theme::parse(id, text).map_err(|e| eprintln!("{}", e)).ok())
+            .collect();
+        let themes = assets.merge_from_disk(THEMES_DIR, "theme", builtins, |t| &t.id, |path| {
+            Theme::load_file(path).map(|theme| vec![theme])
+        });
+
+        ThemeLibrary { themes }
+    }
+
+    pub fn themes(&self) -> &[Theme] {
+        &self.themes
+    }
+
+    /// Theme by id, falling back to the default theme
+    pub fn get(&self, id: Option<&str>) -> Theme {
+        let id = id.unwrap_or(DEFAULT_THEME);
+        self.themes
+            .iter()
+            .find(|t| t.id == id)
+            .or_else(|| self.themes.iter().find(|t| t.id == DEFAULT_THEME))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,112 @@
+// Replays - everything needed to play a game back exactly: seed, rules, walls and inputs
+use crate::config::GameplayConfig;
+use crate::game_state::{Direction, Position};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory (relative to the working directory) replays are saved to
+pub const REPLAYS_DIR: &str = "replays";
+
+/// Bumped whenever a change to the game rules would make old replays play out differently
+pub const REPLAY_VERSION: u32 = 4;
+
+/// A direction change that took effect on a given tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayInput {
+    pub tick: u32,
+    pub direction: Direction,
+}
+
+/// Replay - a recorded game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    /// Seed for food placement
+    pub seed: u64,
+    pub gameplay: GameplayConfig,
+    pub walls: Vec<Position>,
+    pub inputs: Vec<ReplayInput>,
+    /// Ticks played and the final score, filled in as the game runs
+    pub ticks: u32,
+    pub score: u32,
+}
+
+impl Replay {
+    pub fn new(seed: u64, gameplay: GameplayConfig, walls: Vec<Position>) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            gameplay,
+            walls,
+            inputs: Vec::new(),
+            ticks: 0,
+            score: 0,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let replay: Replay =
+            serde_json::from_str(&data).map_err(|e| format!("Invalid replay {:?}: {}", path, e))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "Replay {:?} is version {}, this build plays version {}",
+                path, replay.version, REPLAY_VERSION
+            ));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        }
+        let data = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, data).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    /// Save a finished game as the last replay, and as the best one if it beats it
+    pub fn save_finished(&self) -> Result<(), String> {
+        self.save(&Self::last_path())?;
+
+        let best = Self::best_path();
+        let beats_best = Replay::load(&best).map_or(true, |b| self.score > b.score);
+        if beats_best {
+            self.save(&best)?;
+        }
+        Ok(())
+    }
+
+    /// Save as the ghost for its seed if it beats the one already there
+    pub fn save_ghost(&self) -> Result<(), String> {
+        let path = Self::ghost_path(self.seed);
+        let beats_ghost = Replay::load(&path).map_or(true, |g| self.score > g.score);
+        if beats_ghost {
+            self.save(&path)?;
+        }
+        Ok(())
+    }
+
+    pub fn last_path() -> PathBuf {
+        PathBuf::from(REPLAYS_DIR).join("last.json")
+    }
+
+    pub fn best_path() -> PathBuf {
+        PathBuf::from(REPLAYS_DIR).join("best.json")
+    }
+
+    /// Best run on a seed, raced as a ghost when the seed comes up again
+    pub fn ghost_path(seed: u64) -> PathBuf {
+        PathBuf::from(REPLAYS_DIR).join("ghosts").join(format!("{:016x}.json", seed))
+    }
+
+    /// Direction change that takes effect on `tick`, if any
+    pub fn input_at(&self, tick: u32) -> Option<Direction> {
+        self.inputs
+            .binary_search_by_key(&tick, |input| input.tick)
+            .ok()
+            .map(|i| self.inputs[i].direction)
+    }
+}
@@ -0,0 +1,259 @@
+// Replay rendering - plays a replay back offscreen and writes every frame to disk
+//
+// Draws to a surface in memory with SDL's software renderer, so it needs no
+// window, display or GPU.
+use crate::animation::Clock;
+use crate::assets::AssetResolver;
+use crate::cli::{RenderArgs, RenderFormat};
+use crate::config::Config;
+use crate::fonts::FontSystem;
+use crate::game_state::GameState;
+use crate::locale::LocaleLibrary;
+use crate::persistence::Settings;
+use crate::rendering::sprites::Tileset;
+use crate::rendering::{GameRenderer, ThemeLibrary};
+use crate::replay::Replay;
+use crate::resources;
+use sdl2::image::SaveSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Seconds the final frame is held after the game ends
+const END_HOLD_SECONDS: f32 = 2.0;
+
+/// Render `args.replay` as described by `args`, using the player's theme and accessibility settings
+pub fn render(
+    mut config: Config,
+    mut assets: AssetResolver,
+    args: &RenderArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let replay = Replay::load(&args.replay)?;
+    let (width, height) = args.size.unwrap_or((
+        config.visual.window_width & !1,
+        config.visual.window_height & !1,
+    ));
+
+    let mut canvas = Surface::new(width, height, PixelFormatEnum::RGBA32)?.into_canvas()?;
+
+    // Same pack, theme and text size as the game itself
+    let settings = Settings::load();
+    let pack = settings
+        .asset_pack
+        .as_ref()
+        .and_then(|id| assets.available_packs().into_iter().find(|p| &p.id == id));
+    assets.set_pack(pack);
+    let themes = ThemeLibrary::load(&assets);
+    let mut theme = themes.get(
+        settings
+            .accessibility
+            .color_vision
+            .theme_id()
+            .or(settings.theme.as_deref()),
+    );
+    if let Some(pack) = assets.pack() {
+        theme = theme.with_overrides(&pack.manifest.colors);
+    }
+    let fonts = FontSystem::new(&assets, settings.accessibility.text_scale.point_size())?;
+
+    config.gameplay = replay.gameplay.clone();
+    let mut game = GameState::from_replay(config.clone(), &replay)?;
+    let mut renderer = GameRenderer::new(&config);
+    let tileset = theme.tileset.as_ref().and_then(|spec| {
+        resources::load_texture(&canvas.texture_creator(), &assets, &spec.image)
+            .and_then(|texture| Tileset::new(texture, spec))
+            .map_err(|e| eprintln!("Failed to load tileset for theme {}: {}", theme.id, e))
+            .ok()
+    });
+    renderer.set_tileset(tileset);
+    renderer.set_theme(theme);
+    renderer.set_accessibility(settings.accessibility);
+    renderer.set_locale(LocaleLibrary::load(&assets).get(settings.language.as_deref()));
+    renderer.resize(width, height, fonts.font().height().max(0) as u32 + 16);
+
+    let mut sink = FrameSink::new(args, width, height)?;
+    let mut clock = Clock::new();
+    let frame_seconds = 1.0 / args.fps as f32;
+    let frame_ms = 1000.0 / args.fps as f64;
+    let mut elapsed_ms = 0.0;
+    let mut hold = 0.0;
+
+    loop {
+        // Move exactly as the game did: one step per `current_speed_ms` of replay time
+        let finished = game.finished() || game.tick() >= replay.ticks;
+        if !finished {
+            elapsed_ms += frame_ms;
+            while !game.finished() && elapsed_ms >= game.current_speed_ms as f64 {
+                elapsed_ms -= game.current_speed_ms as f64;
+                if let Some(direction) = replay.input_at(game.tick()) {
+                    game.set_direction(direction);
+                }
+                if game.update() {
+                    renderer.on_food_eaten(&game);
+                }
+                if game.cleared {
+                    renderer.on_board_cleared(&game);
+                } else if game.game_over {
+                    renderer.on_death(&game);
+                }
+            }
+            game.update_interpolation(elapsed_ms as u64);
+        } else {
+            hold += frame_seconds;
+        }
+
+        clock.step(frame_seconds);
+        renderer.update_effects(&game, frame_seconds, &clock);
+        if game.cleared {
+            renderer.render_cleared(&mut canvas, &fonts, &game, &clock);
+        } else {
+            renderer.render_game(&mut canvas, &fonts, &game, None, &clock);
+        }
+        let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32)?;
+        sink.write_frame(&pixels)?;
+
+        if hold >= END_HOLD_SECONDS {
+            break;
+        }
+    }
+
+    renderer.set_tileset(None);
+    if game.score != replay.score {
+        eprintln!(
+            "Replay ended on {} points but was recorded with {}; it may be from an older version",
+            game.score, replay.score
+        );
+    }
+    eprintln!(
+        "Rendered {} frames to {}",
+        sink.frames,
+        args.output.display()
+    );
+    Ok(())
+}
+
+/// Where rendered frames go: a Y4M stream or a directory of PNGs
+struct FrameSink {
+    target: Target,
+    width: u32,
+    height: u32,
+    frames: u32,
+}
+
+enum Target {
+    Y4m(Box<dyn Write>),
+    Png(std::path::PathBuf),
+}
+
+impl FrameSink {
+    fn new(args: &RenderArgs, width: u32, height: u32) -> Result<Self, Box<dyn std::error::Error>> {
+        let target = match args.format {
+            RenderFormat::Y4m => {
+                let mut out: Box<dyn Write> = if args.output == Path::new("-") {
+                    Box::new(BufWriter::new(io::stdout().lock()))
+                } else {
+                    Box::new(BufWriter::new(File::create(&args.output)?))
+                };
+                // Full-range BT.601 with JPEG chroma siting
+                writeln!(
+                    out,
+                    "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C420jpeg",
+                    width, height, args.fps
+                )?;
+                Target::Y4m(out)
+            }
+            RenderFormat::Png => {
+                fs::create_dir_all(&args.output)?;
+                Target::Png(args.output.clone())
+            }
+        };
+        Ok(FrameSink {
+            target,
+            width,
+            height,
+            frames: 0,
+        })
+    }
+
+    /// Write one RGBA32 frame
+    fn write_frame(&mut self, rgba: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        match &mut self.target {
+            Target::Y4m(out) => {
+                out.write_all(b"FRAME\n")?;
+                out.write_all(&rgba_to_yuv420(rgba, self.width, self.height))?;
+            }
+            Target::Png(dir) => {
+                let mut pixels = rgba.to_vec();
+                let surface = Surface::from_data(
+                    &mut pixels,
+                    self.width,
+                    self.height,
+                    self.width * 4,
+                    PixelFormatEnum::RGBA32,
+                )?;
+                surface.save(dir.join(format!("frame-{:05}.png", self.frames)))?;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+impl Drop for FrameSink {
+    fn drop(&mut self) {
+        if let Target::Y4m(out) = &mut self.target {
+            out.flush().ok();
+        }
+    }
+}
+
+/// Convert RGBA to planar YUV 4:2:0, averaging each 2x2 block for chroma
+fn rgba_to_yuv420(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (width, height) = (width as usize, height as usize);
+    let pixel = |x: usize, y: usize| {
+        let i = (y * width + x) * 4;
+        (rgba[i] as f32, rgba[i + 1] as f32, rgba[i + 2] as f32)
+    };
+
+    let mut yuv = Vec::with_capacity(width * height * 3 / 2);
+    for y in 0..height {
+        for x in 0..width {
+            let (r, g, b) = pixel(x, y);
+            yuv.push(
+                (0.299 * r + 0.587 * g + 0.114 * b)
+                    .round()
+                    .clamp(0.0, 255.0) as u8,
+            );
+        }
+    }
+
+    let mut u_plane = Vec::with_capacity(width * height / 4);
+    let mut v_plane = Vec::with_capacity(width * height / 4);
+    for y in (0..height).step_by(2) {
+        for x in (0..width).step_by(2) {
+            let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+            for (px, py) in [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)] {
+                let p = pixel(px, py);
+                r += p.0 / 4.0;
+                g += p.1 / 4.0;
+                b += p.2 / 4.0;
+            }
+            u_plane.push(
+                (128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b)
+                    .round()
+                    .clamp(0.0, 255.0) as u8,
+            );
+            v_plane.push(
+                (128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b)
+                    .round()
+                    .clamp(0.0, 255.0) as u8,
+            );
+        }
+    }
+
+    yuv.extend(u_plane);
+    yuv.extend(v_plane);
+    yuv
+}
@@ -1,8 +1,17 @@
 // Resource loading functions - pure loaders with no state management
-use sdl2::mixer::Chunk;
+use crate::assets::{AssetKind, AssetResolver, AssetSource};
+use sdl2::image::LoadTexture;
+use sdl2::mixer::{Chunk, LoaderRWops, Music};
+use sdl2::render::{Texture, TextureCreator};
+use sdl2::rwops::RWops;
 use sdl2::ttf::{Font, Sdl2TtfContext};
 use std::path::Path;
 
+const MAIN_FONT: &str = "fonts/PressStart2P.ttf";
+const FALLBACK_FONT: &str = "fonts/DejaVuSansMono-Bold.ttf";
+const CLICK_SOUND: &str = "sounds/click.wav";
+const CRUNCH_SOUND: &str = "sounds/crunch.wav";
+
 // =============================================================================
 // Font Resource Loading
 // =============================================================================
@@ -18,35 +27,97 @@ pub fn load_font<'a>(
         .map_err(|e| format!("Failed to load font: {}", e))
 }
 
-/// Load the main game font (Press Start 2P)
-pub fn load_main_font<'a>(ttf_context: &'a Sdl2TtfContext, point_size: u16) -> Result<Font<'a, 'a>, String> {
-    let font_path = Path::new("assets/fonts/PressStart2P.ttf");
-    if !font_path.exists() {
-        return Err(format!("Font file not found: {:?} (cwd: {:?})", font_path, std::env::current_dir()));
+/// Load a font from an in-memory buffer at the specified point size
+pub fn load_font_from_bytes<'a>(
+    ttf_context: &'a Sdl2TtfContext,
+    bytes: &'static [u8],
+    point_size: u16,
+) -> Result<Font<'a, 'static>, String> {
+    let rwops = RWops::from_bytes(bytes)?;
+    ttf_context
+        .load_font_from_rwops(rwops, point_size)
+        .map_err(|e| format!("Failed to load font: {}", e))
+}
+
+/// Load the main game font (Press Start 2P unless the active pack overrides it)
+pub fn load_main_font<'a>(
+    ttf_context: &'a Sdl2TtfContext,
+    assets: &AssetResolver,
+    point_size: u16,
+) -> Result<Font<'a, 'a>, String> {
+    match assets.resolve_role(AssetKind::Font, "main", MAIN_FONT)? {
+        AssetSource::File(path) => load_font(ttf_context, &path, point_size),
+        AssetSource::Bytes(bytes) => load_font_from_bytes(ttf_context, bytes, point_size),
+    }
+}
+
+/// Load the font for characters the main font lacks, such as Cyrillic
+pub fn load_fallback_font<'a>(
+    ttf_context: &'a Sdl2TtfContext,
+    assets: &AssetResolver,
+    point_size: u16,
+) -> Result<Font<'a, 'a>, String> {
+    match assets.resolve_role(AssetKind::Font, "fallback", FALLBACK_FONT)? {
+        AssetSource::File(path) => load_font(ttf_context, &path, point_size),
+        AssetSource::Bytes(bytes) => load_font_from_bytes(ttf_context, bytes, point_size),
     }
-    load_font(ttf_context, font_path, point_size)
 }
 
 // =============================================================================
 // Audio Resource Loading
 // =============================================================================
 
-/// Load the click sound effect
-pub fn load_click_sound() -> Result<Chunk, String> {
-    let path = "assets/sounds/click.wav";
-    if !Path::new(path).exists() {
-        return Err(format!("File not found: {} (cwd: {:?})", path, std::env::current_dir()));
+/// Load a sound effect from wherever the resolver finds it
+fn load_sound(assets: &AssetResolver, role: &str, default: &str) -> Result<Chunk, String> {
+    match assets.resolve_role(AssetKind::Sound, role, default)? {
+        AssetSource::File(path) => Chunk::from_file(&path)
+            .map_err(|e| format!("Failed to load {:?}: {}", path, e)),
+        AssetSource::Bytes(bytes) => RWops::from_bytes(bytes)?
+            .load_wav()
+            .map_err(|e| format!("Failed to load {} sound: {}", role, e)),
     }
-    Chunk::from_file(path)
-        .map_err(|e| format!("Failed to load click.wav: {} (cwd: {:?})", e, std::env::current_dir()))
+}
+
+/// Load the click sound effect
+pub fn load_click_sound(assets: &AssetResolver) -> Result<Chunk, String> {
+    load_sound(assets, "click", CLICK_SOUND)
 }
 
 /// Load the crunch sound effect
-pub fn load_crunch_sound() -> Result<Chunk, String> {
-    let path = "assets/sounds/crunch.wav";
-    if !Path::new(path).exists() {
-        return Err(format!("File not found: {} (cwd: {:?})", path, std::env::current_dir()));
+pub fn load_crunch_sound(assets: &AssetResolver) -> Result<Chunk, String> {
+    load_sound(assets, "crunch", CRUNCH_SOUND)
+}
+
+/// Load a music track from the active asset pack, if it provides one
+pub fn load_music(assets: &AssetResolver, track: &str) -> Option<Result<Music<'static>, String>> {
+    let source = match assets.pack_asset(AssetKind::Music, track)? {
+        Ok(source) => source,
+        Err(e) => return Some(Err(e)),
+    };
+    Some(match source {
+        AssetSource::File(path) => Music::from_file(&path)
+            .map_err(|e| format!("Failed to load {:?}: {}", path, e)),
+        AssetSource::Bytes(bytes) => Music::from_static_bytes(bytes)
+            .map_err(|e| format!("Failed to load {} music: {}", track, e)),
+    })
+}
+
+// =============================================================================
+// Image Resource Loading
+// =============================================================================
+
+/// Load an image (such as a tilesheet) into a texture
+pub fn load_texture<C>(
+    texture_creator: &TextureCreator<C>,
+    assets: &AssetResolver,
+    relative: &str,
+) -> Result<Texture, String> {
+    match assets.resolve(relative)? {
+        AssetSource::File(path) => texture_creator
+            .load_texture(&path)
+            .map_err(|e| format!("Failed to load {:?}: {}", path, e)),
+        AssetSource::Bytes(bytes) => texture_creator
+            .load_texture_bytes(bytes)
+            .map_err(|e| format!("Failed to load {}: {}", relative, e)),
     }
-    Chunk::from_file(path)
-        .map_err(|e| format!("Failed to load crunch.wav: {} (cwd: {:?})", e, std::env::current_dir()))
 }
@@ -0,0 +1,247 @@
+use crate::locale::Locale;
+use crate::persistence::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    Display,
+    Language,
+    Theme,
+    AssetPack,
+    Level,
+    ColorVision,
+    ShapeCues,
+    TextSize,
+    ReducedMotion,
+    FoodSound,
+    Ghost,
+    Credits,
+    Back,
+}
+
+impl SettingsItem {
+    /// Catalog key of the item's label
+    pub fn label_key(self) -> &'static str {
+        match self {
+            SettingsItem::Display => "settings.display",
+            SettingsItem::Language => "settings.language",
+            SettingsItem::Theme => "settings.theme",
+            SettingsItem::AssetPack => "settings.pack",
+            SettingsItem::Level => "settings.level",
+            SettingsItem::ColorVision => "settings.vision",
+            SettingsItem::ShapeCues => "settings.shapes",
+            SettingsItem::TextSize => "settings.text",
+            SettingsItem::ReducedMotion => "settings.reduce_motion",
+            SettingsItem::FoodSound => "settings.food_ping",
+            SettingsItem::Ghost => "settings.ghost",
+            SettingsItem::Credits => "settings.credits",
+            SettingsItem::Back => "settings.back",
+        }
+    }
+}
+
+/// A selectable option value: the id stored in settings and its display name
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub id: String,
+    pub name: String,
+}
+
+pub struct SettingsState {
+    pub selected_item: SettingsItem,
+    pub settings: Settings,
+    pub languages: Vec<Choice>,
+    pub themes: Vec<Choice>,
+    pub packs: Vec<Choice>,
+    pub levels: Vec<Choice>,
+    changed: bool,
+}
+
+impl SettingsState {
+    pub fn new(settings: Settings) -> Self {
+        SettingsState {
+            selected_item: SettingsItem::Display,
+            settings,
+            languages: Vec::new(),
+            themes: Vec::new(),
+            packs: Vec::new(),
+            levels: Vec::new(),
+            changed: false,
+        }
+    }
+
+    pub fn get_items() -> Vec<SettingsItem> {
+        vec![
+            SettingsItem::Display,
+            SettingsItem::Language,
+            SettingsItem::Theme,
+            SettingsItem::AssetPack,
+            SettingsItem::Level,
+            SettingsItem::ColorVision,
+            SettingsItem::ShapeCues,
+            SettingsItem::TextSize,
+            SettingsItem::ReducedMotion,
+            SettingsItem::FoodSound,
+            SettingsItem::Ghost,
+            SettingsItem::Credits,
+            SettingsItem::Back,
+        ]
+    }
+
+    pub fn move_up(&mut self) {
+        let items = Self::get_items();
+        let index = items.iter().position(|i| *i == self.selected_item).unwrap_or(0);
+        self.selected_item = items[(index + items.len() - 1) % items.len()];
+    }
+
+    pub fn move_down(&mut self) {
+        let items = Self::get_items();
+        let index = items.iter().position(|i| *i == self.selected_item).unwrap_or(0);
+        self.selected_item = items[(index + 1) % items.len()];
+    }
+
+    /// Replace the level choices, clearing the selection if it no longer exists
+    pub fn set_levels(&mut self, levels: Vec<Choice>) {
+        if let Some(level) = &self.settings.level
+            && !levels.iter().any(|c| &c.id == level)
+        {
+            self.settings.level = None;
+        }
+        self.levels = levels;
+    }
+
+    /// Step the selected item's value forwards or backwards
+    pub fn adjust(&mut self, forward: bool) {
+        match self.selected_item {
+            SettingsItem::Display => {
+                self.settings.display_mode = self.settings.display_mode.step(forward);
+            }
+            SettingsItem::Language => {
+                self.settings.language = cycle(&self.settings.language, &self.languages, forward);
+            }
+            SettingsItem::Theme => {
+                self.settings.theme = step(&self.settings.theme, &self.themes, forward);
+            }
+            SettingsItem::AssetPack => {
+                self.settings.asset_pack = cycle(&self.settings.asset_pack, &self.packs, forward);
+                self.settings.level = None;
+            }
+            SettingsItem::Level => {
+                self.settings.level = cycle(&self.settings.level, &self.levels, forward);
+            }
+            SettingsItem::ColorVision => {
+                let access = &mut self.settings.accessibility;
+                access.color_vision = access.color_vision.step(forward);
+            }
+            SettingsItem::ShapeCues => {
+                let access = &mut self.settings.accessibility;
+                access.shape_cues = !access.shape_cues;
+            }
+            SettingsItem::TextSize => {
+                let access = &mut self.settings.accessibility;
+                access.text_scale = access.text_scale.step(forward);
+            }
+            SettingsItem::ReducedMotion => {
+                let access = &mut self.settings.accessibility;
+                access.reduced_motion = !access.reduced_motion;
+            }
+            SettingsItem::FoodSound => {
+                let access = &mut self.settings.accessibility;
+                access.food_audio_cue = !access.food_audio_cue;
+            }
+            SettingsItem::Ghost => {
+                self.settings.hide_ghost = !self.settings.hide_ghost;
+            }
+            SettingsItem::Credits | SettingsItem::Back => return,
+        }
+        self.changed = true;
+    }
+
+    /// Switch between windowed and fullscreen from anywhere via the hotkey
+    pub fn toggle_fullscreen(&mut self) {
+        self.settings.display_mode = self.settings.display_mode.toggled();
+        self.changed = true;
+    }
+
+    /// Display text for an item's current value, if it has one
+    pub fn value_label(&self, item: SettingsItem, locale: &Locale) -> Option<String> {
+        let settings = &self.settings;
+        let access = &settings.accessibility;
+        match item {
+            SettingsItem::Display => Some(locale.get(settings.display_mode.label_key()).to_string()),
+            SettingsItem::Language => Some(choice_name(
+                &settings.language,
+                &self.languages,
+                locale.get("settings.auto_language"),
+            )),
+            SettingsItem::Theme => Some(choice_name(&settings.theme, &self.themes, "Neon")),
+            SettingsItem::AssetPack => Some(choice_name(
+                &settings.asset_pack,
+                &self.packs,
+                locale.get("settings.default_pack"),
+            )),
+            SettingsItem::Level => Some(choice_name(&settings.level, &self.levels, locale.get("settings.open_arena"))),
+            SettingsItem::ColorVision => Some(locale.get(access.color_vision.label_key()).to_string()),
+            SettingsItem::ShapeCues => Some(on_off(access.shape_cues, locale)),
+            SettingsItem::TextSize => Some(access.text_scale.label().to_string()),
+            SettingsItem::ReducedMotion => Some(on_off(access.reduced_motion, locale)),
+            SettingsItem::FoodSound => Some(on_off(access.food_audio_cue, locale)),
+            SettingsItem::Ghost => Some(on_off(!settings.hide_ghost, locale)),
+            SettingsItem::Credits | SettingsItem::Back => None,
+        }
+    }
+
+    /// Returns true once after any setting was changed
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+/// Index one place forward or back from `index` among `count`, wrapping at either end
+fn wrap_index(index: usize, count: usize, forward: bool) -> usize {
+    if forward {
+        (index + 1) % count
+    } else {
+        (index + count - 1) % count
+    }
+}
+
+/// The value after (or before) `current` in `all`, wrapping at either end
+pub fn next_in<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let index = all.iter().position(|v| *v == current).unwrap_or(0);
+    all[wrap_index(index, all.len(), forward)]
+}
+
+/// Cycle through `None` followed by each choice
+fn cycle(current: &Option<String>, choices: &[Choice], forward: bool) -> Option<String> {
+    let count = choices.len() + 1;
+    let index = current
+        .as_ref()
+        .and_then(|id| choices.iter().position(|c| &c.id == id))
+        .map_or(0, |i| i + 1);
+    wrap_index(index, count, forward)
+        .checked_sub(1)
+        .map(|i| choices[i].id.clone())
+}
+
+/// Cycle through the choices only; `None` counts as the first choice
+fn step(current: &Option<String>, choices: &[Choice], forward: bool) -> Option<String> {
+    if choices.is_empty() {
+        return current.clone();
+    }
+    let index = current
+        .as_ref()
+        .and_then(|id| choices.iter().position(|c| &c.id == id))
+        .unwrap_or(0);
+    Some(choices[wrap_index(index, choices.len(), forward)].id.clone())
+}
+
+fn choice_name(current: &Option<String>, choices: &[Choice], none_label: &str) -> String {
+    current
+        .as_ref()
+        .and_then(|id| choices.iter().find(|c| &c.id == id))
+        .map_or_else(|| none_label.to_string(), |c| c.name.clone())
+}
+
+fn on_off(value: bool, locale: &Locale) -> String {
+    locale.get(if value { "settings.on" } else { "settings.off" }).to_string()
+}
@@ -0,0 +1,80 @@
+use crate::locale::Locale;
+use crate::persistence::{DAILY_MODE, LEVEL_MODE_PREFIX, OPEN_ARENA_MODE, Profile};
+
+/// Most games the score history chart shows
+pub const CHART_GAMES: usize = 30;
+
+pub struct StatsState {
+    pub profile: Profile,
+}
+
+impl StatsState {
+    pub fn new() -> Self {
+        StatsState {
+            profile: Profile::load(),
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.profile = Profile::load();
+    }
+
+    /// The lifetime totals as lines of text, one stat per line
+    pub fn summary(&self, locale: &Locale) -> Vec<String> {
+        let profile = &self.profile;
+        let value = |key: &str, value: String| locale.format(key, &[("value", value)]);
+        let mut lines = vec![
+            value("stats.games_played", profile.games_played.to_string()),
+            value("stats.total_food", profile.total_food.to_string()),
+            value("stats.total_time", format_duration(profile.total_time_ms)),
+            value("stats.average_game", format_duration(profile.average_game_ms())),
+            value("stats.longest_snake", profile.longest_snake.to_string()),
+            locale.format(
+                "stats.deaths",
+                &[
+                    ("wall", profile.wall_deaths.to_string()),
+                    ("self", profile.self_deaths.to_string()),
+                ],
+            ),
+            value("stats.boards_cleared", profile.boards_cleared.to_string()),
+        ];
+        for (mode, score) in &profile.best_by_mode {
+            let mode = match mode.as_str() {
+                OPEN_ARENA_MODE => locale.get("settings.open_arena"),
+                DAILY_MODE => locale.get("daily.title"),
+                _ => mode.strip_prefix(LEVEL_MODE_PREFIX).unwrap_or(mode),
+            };
+            lines.push(locale.format(
+                "stats.best",
+                &[("mode", mode.to_string()), ("score", score.to_string())],
+            ));
+        }
+        lines
+    }
+
+    /// Scores of the last `CHART_GAMES` games paired with the best score up to each, oldest first
+    pub fn chart(&self) -> Vec<(u32, u32)> {
+        let mut best = 0;
+        let points: Vec<(u32, u32)> = self
+            .profile
+            .history
+            .iter()
+            .map(|game| {
+                best = best.max(game.score);
+                (game.score, best)
+            })
+            .collect();
+        points[points.len().saturating_sub(CHART_GAMES)..].to_vec()
+    }
+}
+
+/// Game time as `m:ss`, or `h:mm:ss` from an hour up
+pub fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
@@ -0,0 +1,623 @@
+// Terminal frontend - plays the game in a terminal with ANSI escape codes
+//
+// Drives the same states and input handling as the SDL app; only drawing and
+// keyboard input differ. Raw keyboard input is switched on with `stty`, so this
+// needs a Unix terminal.
+use crate::achievements::{self, Achievement, AchievementTracker};
+use crate::achievements_state::AchievementsState;
+use crate::assets::AssetResolver;
+use crate::config::Config;
+use crate::daily_state::DailyState;
+use crate::game_state::{Direction, GameState};
+use crate::ghost::Ghost;
+use crate::input::GameInput;
+use crate::input_handler::{AppState, InputHandler};
+use crate::leaderboard_state::LeaderboardState;
+use crate::level;
+use crate::locale::{Locale, LocaleLibrary};
+use crate::menu_state::MenuState;
+use crate::persistence::{self, Settings};
+use crate::rendering::ThemeLibrary;
+use crate::rendering::theme::{Theme, lerp_color};
+use crate::settings_state::SettingsState;
+use crate::stats_state::{StatsState, format_duration};
+use sdl2::pixels::Color;
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between frames; the snake still moves at `current_speed_ms`
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How long an unlocked achievement is announced next to the score
+const TOAST_DURATION: Duration = Duration::from_millis(3500);
+
+/// How much of the snake's colour shows through in the ghost's cells
+const GHOST_SHADE: f32 = 0.3;
+
+/// How often the terminal size is checked for resizes
+const SIZE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Rows the board takes besides the grid: the HUD, two edges, message, hint and the row the cursor ends on
+const BOARD_EXTRA_ROWS: usize = 6;
+
+/// Ctrl-C arrives as a plain byte in raw mode
+const CTRL_C: u8 = 3;
+const ESC: u8 = 0x1b;
+
+/// Block characters for the stats sparkline, lowest to highest
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Keys the terminal understands, beyond what `InputHandler` handles
+enum Key {
+    Input(GameInput),
+    Quit,
+}
+
+/// Terminal in raw mode on the alternate screen, restored when dropped
+struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enter() -> Result<Self, String> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return Err("The terminal frontend needs an interactive terminal".to_string());
+        }
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+
+        // Alternate screen, hidden cursor
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush().ok();
+        Ok(RawTerminal {
+            saved: saved.trim().to_string(),
+        })
+    }
+
+    /// Terminal size as (columns, rows)
+    fn size() -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (rows, columns) = size.trim().split_once(' ')?;
+                Some((columns.parse().ok()?, rows.parse().ok()?))
+            })
+            .unwrap_or((80, 24))
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[0m\x1b[?25h\x1b[?1049l");
+        io::stdout().flush().ok();
+        if let Err(e) = stty(&[&self.saved]) {
+            eprintln!("Failed to restore the terminal: {}", e);
+        }
+    }
+}
+
+/// Run `stty` on the controlling terminal and return what it printed
+fn stty(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Failed to run stty: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("stty {} failed", args.join(" ")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Read stdin on a background thread so the game loop never blocks on it
+fn spawn_key_reader() -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buffer = [0u8; 64];
+        while let Ok(n) = stdin.read(&mut buffer) {
+            if n == 0 || sender.send(buffer[..n].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Decode the keys in one chunk of terminal input
+fn decode_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let key = match bytes[i] {
+            // Arrow keys: ESC [ A..D, or ESC O A..D in application mode
+            ESC if i + 2 < bytes.len() && matches!(bytes[i + 1], b'[' | b'O') => {
+                i += 2;
+                match bytes[i] {
+                    b'A' => Some(Key::Input(GameInput::Up)),
+                    b'B' => Some(Key::Input(GameInput::Down)),
+                    b'C' => Some(Key::Input(GameInput::Right)),
+                    b'D' => Some(Key::Input(GameInput::Left)),
+                    _ => None,
+                }
+            }
+            ESC => Some(Key::Input(GameInput::Back)),
+            b'w' | b'W' | b'k' => Some(Key::Input(GameInput::Up)),
+            b's' | b'S' | b'j' => Some(Key::Input(GameInput::Down)),
+            b'a' | b'A' | b'h' => Some(Key::Input(GameInput::Left)),
+            b'd' | b'D' | b'l' => Some(Key::Input(GameInput::Right)),
+            b'\r' | b'\n' | b' ' => Some(Key::Input(GameInput::Select)),
+            b'r' | b'R' => Some(Key::Input(GameInput::Retry)),
+            b'q' | b'Q' | CTRL_C => Some(Key::Quit),
+            _ => None,
+        };
+        keys.extend(key);
+        i += 1;
+    }
+    keys
+}
+
+/// Play in the terminal until the player quits
+pub fn run(config: Config, mut assets: AssetResolver) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = Settings::load();
+    let pack = settings
+        .asset_pack
+        .as_ref()
+        .and_then(|id| assets.available_packs().into_iter().find(|p| &p.id == id));
+    assets.set_pack(pack);
+    let theme = ThemeLibrary::load(&assets).get(settings.theme.as_deref());
+    let locale = LocaleLibrary::load(&assets).get(settings.language.as_deref());
+
+    let mut app_state = AppState::MainMenu;
+    let mut menu_state = MenuState::new();
+    let mut game_state = GameState::new(config);
+    game_state.set_walls(level::selected_walls(settings.level.as_deref(), assets.pack()));
+    let mode = level::mode_name(settings.level.as_deref());
+    let mut leaderboard_state = LeaderboardState::new();
+    let mut daily_state = DailyState::new();
+    let mut stats_state = StatsState::new();
+    let achievement_definitions = achievements::load_definitions(&assets);
+    let mut achievements = AchievementTracker::new(achievement_definitions.clone());
+    let mut achievements_state = AchievementsState::new(achievement_definitions);
+    let mut toast: Option<(String, Instant)> = None;
+    let hide_ghost = settings.hide_ghost;
+    let mut settings_state = SettingsState::new(settings);
+    let mut ghost: Option<Ghost> = None;
+    let mut ghost_checked = false;
+
+    let terminal = RawTerminal::enter()?;
+    let keys = spawn_key_reader();
+    let mut last_update = Instant::now();
+    let mut size = RawTerminal::size();
+    let mut last_size_check = last_update;
+    let mut last_frame = String::new();
+
+    'running: loop {
+        while let Ok(bytes) = keys.try_recv() {
+            for key in decode_keys(&bytes) {
+                let input = match key {
+                    Key::Quit => break 'running,
+                    Key::Input(input) => input,
+                };
+                // Settings are changed in the windowed game; here they only show a note
+                if matches!(app_state, AppState::Settings | AppState::Credits) {
+                    if matches!(input, GameInput::Back | GameInput::Select) {
+                        app_state = AppState::MainMenu;
+                    }
+                    continue;
+                }
+                let quit = InputHandler::handle_input(
+                    &mut app_state,
+                    &mut menu_state,
+                    &mut game_state,
+                    &mut leaderboard_state,
+                    &mut daily_state,
+                    &mut stats_state,
+                    &mut achievements_state,
+                    &mut settings_state,
+                    input,
+                    &|| {},
+                );
+                if quit {
+                    break 'running;
+                }
+            }
+        }
+
+        if last_size_check.elapsed() >= SIZE_CHECK_INTERVAL {
+            size = RawTerminal::size();
+            last_size_check = Instant::now();
+        }
+        let (needed_columns, needed_rows) = board_size(&game_state);
+        let too_small = size.0 < needed_columns || size.1 < needed_rows;
+        // A board that doesn't fit waits paused instead of being played half hidden
+        if too_small && app_state == AppState::Playing && !game_state.finished() && !game_state.paused {
+            game_state.toggle_pause();
+        }
+
+        if app_state == AppState::Playing {
+            // A fresh game looks up the best earlier run on its seed
+            if game_state.tick() == 0 {
+                if !ghost_checked {
+                    ghost = if hide_ghost { None } else { Ghost::load(&game_state) };
+                    ghost_checked = true;
+                }
+            } else {
+                ghost_checked = false;
+            }
+
+            let now = Instant::now();
+            if now.duration_since(last_update) >= Duration::from_millis(game_state.current_speed_ms as u64) {
+                let was_finished = game_state.finished();
+                let food_eaten = game_state.update();
+                let mut unlocked = achievements.step(&game_state, food_eaten);
+                if game_state.finished() && !was_finished {
+                    if game_state.cleared {
+                        app_state = AppState::Cleared;
+                    }
+                    persistence::save_finished_game(&game_state, &mode);
+                    unlocked.extend(achievements.finish(&game_state));
+                }
+                if let Some(achievement) = unlocked.last() {
+                    toast = Some((announce(&locale, achievement), now));
+                }
+                last_update = now;
+            }
+            if let Some(ghost) = &mut ghost {
+                ghost.sync(&game_state);
+            }
+        }
+
+        if toast.as_ref().is_some_and(|(_, at)| at.elapsed() >= TOAST_DURATION) {
+            toast = None;
+        }
+
+        // Only send frames that changed, to keep SSH sessions light
+        let screen = Screen::new(&theme, &locale, size);
+        let frame = match app_state {
+            AppState::MainMenu => screen.menu(&menu_state),
+            AppState::Playing | AppState::Cleared if too_small => screen.too_small(needed_columns, needed_rows),
+            AppState::Playing => screen.game(&game_state, ghost.as_ref(), toast.as_ref().map(|(text, _)| text.as_str())),
+            AppState::Cleared => screen.cleared(&game_state),
+            AppState::Leaderboard => screen.leaderboard(&leaderboard_state),
+            AppState::Daily => screen.daily(&daily_state),
+            AppState::Stats => screen.stats(&stats_state),
+            AppState::Achievements => screen.achievements(&achievements_state),
+            AppState::Settings | AppState::Credits => screen.settings_note(),
+        };
+        if frame != last_frame {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(frame.as_bytes())?;
+            stdout.flush()?;
+            last_frame = frame;
+        }
+
+        thread::sleep(FRAME_INTERVAL);
+    }
+
+    drop(terminal);
+    Ok(())
+}
+
+/// Announcement for an unlocked achievement, shown next to the score
+fn announce(locale: &Locale, achievement: &Achievement) -> String {
+    format!("{}: {}", locale.get("achievements.unlocked"), achievement.name(locale))
+}
+
+/// Screen - builds one frame of ANSI output for the current terminal size
+struct Screen<'a> {
+    theme: &'a Theme,
+    locale: &'a Locale,
+    columns: usize,
+    rows: usize,
+}
+
+impl<'a> Screen<'a> {
+    fn new(theme: &'a Theme, locale: &'a Locale, (columns, rows): (usize, usize)) -> Self {
+        Screen {
+            theme,
+            locale,
+            columns,
+            rows,
+        }
+    }
+
+    /// A line of text from the catalog
+    fn text(&self, key: &str, color: Color) -> (String, Color) {
+        (self.locale.get(key).to_string(), color)
+    }
+
+    fn menu(&self, menu: &MenuState) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 4];
+        lines.push(self.text("menu.title", self.theme.text.0));
+        lines.push((String::new(), self.theme.text.0));
+
+        for item in MenuState::get_items() {
+            let text = self.locale.get(item.label_key());
+            let line = if item == menu.selected_item {
+                (format!("> {} <", text), self.theme.text.0)
+            } else {
+                (text.to_string(), self.theme.text_dim.0)
+            };
+            lines.push(line);
+        }
+
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("terminal.menu_hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    fn leaderboard(&self, state: &LeaderboardState) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 4];
+        lines.push(self.text("leaderboard.title", self.theme.text.0));
+        lines.push((String::new(), self.theme.text.0));
+
+        if state.leaderboard.entries.is_empty() {
+            lines.push(self.text("leaderboard.empty", self.theme.text_dim.0));
+        }
+        for (i, entry) in state.leaderboard.entries.iter().enumerate() {
+            let text = LeaderboardState::entry_text(entry, i + 1, self.locale);
+            let color = if entry.cleared { self.theme.highlight.0 } else { self.theme.text.0 };
+            lines.push((text, color));
+        }
+
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("leaderboard.hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    /// Today's rules and attempt, then earlier days
+    fn daily(&self, state: &DailyState) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 6];
+        lines.push(self.text("daily.title", self.theme.text.0));
+        lines.push((state.challenge.date.clone(), self.theme.text_dim.0));
+        lines.push((String::new(), self.theme.text.0));
+        for line in state.rules(self.locale) {
+            lines.push((line, self.theme.text.0));
+        }
+        lines.push((String::new(), self.theme.text.0));
+        lines.push((state.status(self.locale), self.theme.highlight.0));
+
+        let mut previous = state.previous().peekable();
+        if previous.peek().is_some() {
+            lines.push((String::new(), self.theme.text.0));
+            lines.push(self.text("daily.history", self.theme.text_dim.0));
+        }
+        for result in previous {
+            let text = self.locale.format(
+                "daily.entry",
+                &[("date", result.date.clone()), ("score", result.score.to_string())],
+            );
+            lines.push((text, self.theme.text.0));
+        }
+
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("daily.hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    /// Totals, then recent scores as a sparkline of block characters
+    fn stats(&self, state: &StatsState) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 6];
+        lines.push(self.text("stats.title", self.theme.text.0));
+        lines.push((String::new(), self.theme.text.0));
+
+        if state.profile.games_played == 0 {
+            lines.push(self.text("stats.empty", self.theme.text_dim.0));
+        } else {
+            for line in state.summary(self.locale) {
+                lines.push((line, self.theme.text.0));
+            }
+            let points = state.chart();
+            let max = points.iter().map(|&(score, _)| score).max().unwrap_or(0).max(1);
+            let chart: String = points
+                .iter()
+                .map(|&(score, _)| SPARKS[(score as usize * (SPARKS.len() - 1)) / max as usize])
+                .collect();
+            lines.push((String::new(), self.theme.text.0));
+            lines.push((
+                self.locale.format(
+                    "stats.sparkline",
+                    &[("count", points.len().to_string()), ("chart", chart)],
+                ),
+                self.theme.highlight.0,
+            ));
+        }
+
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("stats.hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    /// Every achievement with its description, scrolled to keep the selected one in view
+    fn achievements(&self, state: &AchievementsState) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); 1];
+        lines.push(self.text("achievements.title", self.theme.text.0));
+        let progress = self.locale.format(
+            "achievements.progress",
+            &[
+                ("unlocked", state.unlocked_count().to_string()),
+                ("total", state.definitions.len().to_string()),
+            ],
+        );
+        lines.push((progress, self.theme.text_dim.0));
+        lines.push((String::new(), self.theme.text.0));
+
+        let visible = (self.rows.saturating_sub(lines.len() + 3) / 2).max(1);
+        let first = state.selected.saturating_sub(visible - 1);
+        for (i, achievement) in state.definitions.iter().enumerate().skip(first).take(visible) {
+            let name = achievement.name(self.locale);
+            let name = if i == state.selected { format!("> {} <", name) } else { name.to_string() };
+            let color = if state.is_unlocked(achievement) { self.theme.highlight.0 } else { self.theme.text_dim.0 };
+            lines.push((name, color));
+            lines.push((state.description(achievement, self.locale), self.theme.text_dim.0));
+        }
+
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("achievements.hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    fn settings_note(&self) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 3];
+        lines.push(self.text("settings.title", self.theme.text.0));
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("terminal.settings_note", self.theme.text_dim.0));
+        lines.push((String::new(), self.theme.text.0));
+        lines.push(self.text("credits.hint", self.theme.text_dim.0));
+        self.text_frame(&lines)
+    }
+
+    /// The game being played, or its end when the snake died
+    fn game(&self, game: &GameState, ghost: Option<&Ghost>, toast: Option<&str>) -> String {
+        let (message, hint) = if game.game_over {
+            let final_score = self.locale.format("game.final_score", &[("score", game.score.to_string())]);
+            (
+                format!("{}  {}", self.locale.get("game.game_over"), final_score),
+                "game.game_over_hint",
+            )
+        } else if game.paused {
+            (self.locale.get("game.paused").to_string(), "game.resume_hint")
+        } else {
+            (String::new(), "terminal.game_hint")
+        };
+        self.board(game, ghost, toast, &message, hint)
+    }
+
+    /// The filled board of a win, with its score and time
+    fn cleared(&self, game: &GameState) -> String {
+        let final_score = self.locale.format("game.final_score", &[("score", game.score.to_string())]);
+        let time = self.locale.format("game.cleared_time", &[("time", format_duration(game.elapsed_ms))]);
+        let message = format!("{}  {}  {}", self.locale.get("game.cleared"), final_score, time);
+        self.board(game, None, None, &message, "game.game_over_hint")
+    }
+
+    /// In place of a board that doesn't fit, the size it needs
+    fn too_small(&self, columns: usize, rows: usize) -> String {
+        let mut lines = vec![(String::new(), self.theme.text.0); self.rows / 3];
+        let size = [("columns", columns.to_string()), ("rows", rows.to_string())];
+        lines.push((self.locale.format("terminal.too_small", &size), self.theme.highlight.0));
+        self.text_frame(&lines)
+    }
+
+    /// The playfield with two columns per cell so cells come out roughly square
+    fn board(&self, game: &GameState, ghost: Option<&Ghost>, toast: Option<&str>, message: &str, hint: &str) -> String {
+        let (grid_width, grid_height) = game.grid_dimensions();
+        let (board_width, _) = board_size(game);
+        let indent = " ".repeat(self.columns.saturating_sub(board_width) / 2);
+        let background = self.theme.background.0;
+
+        let mut cells = vec![background; (grid_width * grid_height) as usize];
+        let mut put = |x: i32, y: i32, color: Color| {
+            if x >= 0 && y >= 0 && (x as u32) < grid_width && (y as u32) < grid_height {
+                cells[(y as u32 * grid_width + x as u32) as usize] = color;
+            }
+        };
+        for wall in &game.walls {
+            put(wall.x, wall.y, self.theme.wall.0);
+        }
+        if let Some(ghost) = ghost.filter(|g| !g.finished()) {
+            let body = &ghost.game().snake;
+            for (i, segment) in body.iter().enumerate().rev() {
+                let color = self.theme.snake_segment(i, body.len());
+                put(segment.x, segment.y, lerp_color(background, color, GHOST_SHADE));
+            }
+        }
+        if !game.cleared {
+            put(game.food.x, game.food.y, self.theme.food.normal.0);
+        }
+        let len = game.snake.len();
+        for (i, segment) in game.snake.iter().enumerate().rev() {
+            put(segment.x, segment.y, self.theme.snake_segment(i, len));
+        }
+
+        let mut out = String::from("\x1b[H");
+        let mut hud = self.locale.format("game.score", &[("score", game.score.to_string())]);
+        if game.config().input.show_queue {
+            let turns: String = game.queued_turns().map(arrow).collect();
+            hud = format!("{} {}", hud, turns);
+        }
+        if let Some(run) = game.daily() {
+            let key = if run.scored { "daily.hud" } else { "daily.practice" };
+            hud = format!("{}   {}", hud, self.locale.format(key, &[("date", run.challenge.date.clone())]));
+        }
+        if let Some(ghost) = ghost {
+            let delta = format!("{:+}", ghost.score_delta(game));
+            hud = format!("{}   {}", hud, self.locale.format("game.ghost", &[("delta", delta)]));
+        }
+        if let Some(toast) = toast {
+            hud = format!("{}   {}", hud, toast);
+        }
+        self.line(&mut out, &format!("{}{}", indent, hud), self.theme.text.0);
+
+        let border = self.theme.grid.0;
+        let edge = format!("{}{}{}", indent, bg(border), " ".repeat(board_width));
+        let _ = write!(out, "{}{}\x1b[K\r\n", edge, bg(background));
+        for y in 0..grid_height {
+            let _ = write!(out, "{}{}  ", indent, bg(border));
+            let mut current = border;
+            for x in 0..grid_width {
+                let color = cells[(y * grid_width + x) as usize];
+                if color != current {
+                    out.push_str(&bg(color));
+                    current = color;
+                }
+                out.push_str("  ");
+            }
+            let _ = write!(out, "{}  {}\x1b[K\r\n", bg(border), bg(background));
+        }
+        let _ = write!(out, "{}{}\x1b[K\r\n", edge, bg(background));
+
+        self.line(&mut out, &format!("{}{}", indent, message), self.theme.highlight.0);
+        self.line(&mut out, &format!("{}{}", indent, self.locale.get(hint)), self.theme.text_dim.0);
+        out.push_str("\x1b[J");
+        out
+    }
+
+    /// Lines of centred text, each in its own colour
+    fn text_frame(&self, lines: &[(String, Color)]) -> String {
+        let mut out = String::from("\x1b[H");
+        for (text, color) in lines {
+            let indent = " ".repeat(self.columns.saturating_sub(text.chars().count()) / 2);
+            self.line(&mut out, &format!("{}{}", indent, text), *color);
+        }
+        out.push_str("\x1b[J");
+        out
+    }
+
+    /// One line of text on the theme background, clearing the rest of the row
+    fn line(&self, out: &mut String, text: &str, color: Color) {
+        let _ = write!(out, "{}{}{}\x1b[K\r\n", bg(self.theme.background.0), fg(color), text);
+    }
+}
+
+/// Columns and rows the board of `game` needs, border and text lines included
+fn board_size(game: &GameState) -> (usize, usize) {
+    let (grid_width, grid_height) = game.grid_dimensions();
+    ((grid_width as usize + 2) * 2, grid_height as usize + BOARD_EXTRA_ROWS)
+}
+
+/// Arrow character pointing `direction`
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '↑',
+        Direction::Down => '↓',
+        Direction::Left => '←',
+        Direction::Right => '→',
+    }
+}
+
+/// 24-bit foreground colour escape
+fn fg(color: Color) -> String {
+    format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b)
+}
+
+/// 24-bit background colour escape
+fn bg(color: Color) -> String {
+    format!("\x1b[48;2;{};{};{}m", color.r, color.g, color.b)
+}